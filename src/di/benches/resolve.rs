@@ -0,0 +1,59 @@
+// compares resolving a service by type with resolving it through a handle. a handle only
+// skips hashing the service type and looking up its registrations; creating the service,
+// including the allocation of its type-erased instance, is the same for both paths.
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use di::*;
+
+trait Service {
+    fn value(&self) -> usize;
+}
+
+struct ServiceImpl(usize);
+
+impl Service for ServiceImpl {
+    fn value(&self) -> usize {
+        self.0
+    }
+}
+
+fn new_provider(lifetime: ServiceLifetime) -> ServiceProvider {
+    ServiceCollection::new()
+        .add(
+            ServiceDescriptorBuilder::<dyn Service, ServiceImpl>::new(
+                lifetime,
+                Type::of::<ServiceImpl>(),
+            )
+            .from(|_| ServiceRef::new(ServiceImpl(1))),
+        )
+        .build_provider()
+        .unwrap()
+}
+
+fn lookup(c: &mut Criterion, name: &str, lifetime: ServiceLifetime) {
+    let mut group = c.benchmark_group(name);
+    let provider = new_provider(lifetime);
+    let handle = provider.handle::<dyn Service>().unwrap();
+
+    group.bench_function("get", |b| {
+        b.iter(|| black_box(provider.get_required::<dyn Service>().value()))
+    });
+    group.bench_function("handle", |b| {
+        b.iter(|| black_box(handle.get_required(&provider).value()))
+    });
+    group.finish();
+}
+
+fn lookup_singleton(c: &mut Criterion) {
+    lookup(c, "lookup_singleton", ServiceLifetime::Singleton);
+}
+
+fn lookup_scoped(c: &mut Criterion) {
+    lookup(c, "lookup_scoped", ServiceLifetime::Scoped);
+}
+
+fn lookup_transient(c: &mut Criterion) {
+    lookup(c, "lookup_transient", ServiceLifetime::Transient);
+}
+
+criterion_group!(benches, lookup_singleton, lookup_scoped, lookup_transient);
+criterion_main!(benches);
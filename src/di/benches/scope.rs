@@ -0,0 +1,73 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use di::*;
+
+trait Service {
+    fn value(&self) -> usize;
+}
+
+struct ServiceImpl(usize);
+
+impl Service for ServiceImpl {
+    fn value(&self) -> usize {
+        self.0
+    }
+}
+
+fn new_provider(registrations: usize) -> ServiceProvider {
+    let mut services = ServiceCollection::new();
+
+    for i in 0..registrations {
+        if i % 2 == 0 {
+            services.add(
+                scoped::<dyn Service, ServiceImpl>().from(move |_| ServiceRef::new(ServiceImpl(i))),
+            );
+        } else {
+            services.add(
+                singleton::<dyn Service, ServiceImpl>()
+                    .from(move |_| ServiceRef::new(ServiceImpl(i))),
+            );
+        }
+    }
+
+    services.build_provider().unwrap()
+}
+
+fn create_scope(c: &mut Criterion) {
+    let mut group = c.benchmark_group("create_scope");
+
+    for registrations in [10, 100, 1000] {
+        let provider = new_provider(registrations);
+
+        group.bench_with_input(
+            BenchmarkId::from_parameter(registrations),
+            &provider,
+            |b, provider| b.iter(|| black_box(provider.create_scope())),
+        );
+    }
+
+    group.finish();
+}
+
+fn create_scope_and_resolve(c: &mut Criterion) {
+    let mut group = c.benchmark_group("create_scope_and_resolve");
+
+    for registrations in [10, 100, 1000] {
+        let provider = new_provider(registrations);
+
+        group.bench_with_input(
+            BenchmarkId::from_parameter(registrations),
+            &provider,
+            |b, provider| {
+                b.iter(|| {
+                    let scope = provider.create_scope();
+                    black_box(scope.get_required::<dyn Service>().value())
+                })
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, create_scope, create_scope_and_resolve);
+criterion_main!(benches);
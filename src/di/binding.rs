@@ -0,0 +1,666 @@
+use crate::{ServiceCollection, ServiceDescriptor, ServiceLifetime};
+use std::collections::BTreeMap;
+
+#[cfg(any(feature = "toml", feature = "json"))]
+use std::{fs, io, path::Path};
+
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+
+pub(crate) struct NamedImplementation {
+    service_name: String,
+    name: String,
+    descriptor: ServiceDescriptor,
+}
+
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum BindingValue {
+    Name(String),
+    Table {
+        implementation: String,
+        lifetime: Option<String>,
+    },
+}
+
+/// Represents the binding of a named service to one of its named implementations.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Deserialize), serde(from = "BindingValue"))]
+pub struct ServiceBinding {
+    implementation: String,
+    lifetime: Option<String>,
+}
+
+impl ServiceBinding {
+    /// Initializes a new service binding.
+    ///
+    /// # Arguments
+    ///
+    /// * `implementation` - The name of the bound implementation
+    pub fn new<S: AsRef<str>>(implementation: S) -> Self {
+        Self {
+            implementation: implementation.as_ref().to_owned(),
+            lifetime: None,
+        }
+    }
+
+    /// Sets the lifetime of the bound implementation, which overrides the registered lifetime.
+    ///
+    /// # Arguments
+    ///
+    /// * `lifetime` - The name of the lifetime, which is one of `singleton`, `scoped`, `transient`, `tenant`, or `per-thread`
+    pub fn with_lifetime<S: AsRef<str>>(mut self, lifetime: S) -> Self {
+        self.lifetime = Some(lifetime.as_ref().to_owned());
+        self
+    }
+
+    /// Gets the name of the bound implementation.
+    pub fn implementation(&self) -> &str {
+        &self.implementation
+    }
+
+    /// Gets the name of the lifetime of the bound implementation, if any.
+    pub fn lifetime(&self) -> Option<&str> {
+        self.lifetime.as_deref()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<BindingValue> for ServiceBinding {
+    fn from(value: BindingValue) -> Self {
+        match value {
+            BindingValue::Name(implementation) => Self::new(implementation),
+            BindingValue::Table {
+                implementation,
+                lifetime,
+            } => Self {
+                implementation,
+                lifetime,
+            },
+        }
+    }
+}
+
+impl From<&str> for ServiceBinding {
+    fn from(implementation: &str) -> Self {
+        Self::new(implementation)
+    }
+}
+
+/// Represents a configuration document that binds service names to named implementations.
+///
+/// # Remarks
+///
+/// When the **serde** feature is enabled, the bindings can be deserialized from any format,
+/// such as TOML or JSON, where each key is a service name and each value is either the name
+/// of an implementation or a table with an `implementation` and an optional `lifetime`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Deserialize), serde(transparent))]
+pub struct ServiceBindings {
+    bindings: BTreeMap<String, ServiceBinding>,
+}
+
+impl ServiceBindings {
+    /// Initializes new, empty service bindings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Binds a service name to an implementation.
+    ///
+    /// # Arguments
+    ///
+    /// * `service_name` - The name of the service
+    /// * `binding` - The [binding](struct.ServiceBinding.html) of the service
+    pub fn bind<S: AsRef<str>, B: Into<ServiceBinding>>(
+        mut self,
+        service_name: S,
+        binding: B,
+    ) -> Self {
+        self.bindings
+            .insert(service_name.as_ref().to_owned(), binding.into());
+        self
+    }
+
+    /// Gets an iterator of the service names and their bindings, ordered by service name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &ServiceBinding)> {
+        self.bindings.iter().map(|(k, v)| (k.as_str(), v))
+    }
+
+    /// Reads service bindings from the specified file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file to read
+    ///
+    /// # Remarks
+    ///
+    /// This function is only available with the **toml** or **json** feature. The format of the
+    /// file is determined by its `.toml` or `.json` extension. A file with any other extension
+    /// results in an error of kind
+    /// [InvalidInput](https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.InvalidInput).
+    #[cfg(any(feature = "toml", feature = "json"))]
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)?;
+
+        match path.extension().and_then(|extension| extension.to_str()) {
+            #[cfg(feature = "toml")]
+            Some("toml") => Self::from_toml(&text),
+            #[cfg(feature = "json")]
+            Some("json") => Self::from_json(&text),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "The format of the service bindings in '{}' is not supported",
+                    path.display()
+                ),
+            )),
+        }
+    }
+
+    /// Reads service bindings from the specified TOML document.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The TOML document to read
+    ///
+    /// # Remarks
+    ///
+    /// This function is only available with the **toml** feature. A document that does not
+    /// contain valid bindings results in an error of kind
+    /// [InvalidData](https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.InvalidData).
+    #[cfg(feature = "toml")]
+    pub fn from_toml(text: &str) -> io::Result<Self> {
+        toml::from_str(text).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+
+    /// Reads service bindings from the specified JSON document.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The JSON document to read
+    ///
+    /// # Remarks
+    ///
+    /// This function is only available with the **json** feature. A document that does not
+    /// contain valid bindings results in an error of kind
+    /// [InvalidData](https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.InvalidData).
+    #[cfg(feature = "json")]
+    pub fn from_json(text: &str) -> io::Result<Self> {
+        serde_json::from_str(text)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+}
+
+fn parse_lifetime(name: &str) -> Option<ServiceLifetime> {
+    match name.to_ascii_lowercase().as_str() {
+        "singleton" => Some(ServiceLifetime::Singleton),
+        "scoped" => Some(ServiceLifetime::Scoped),
+        "transient" => Some(ServiceLifetime::Transient),
+        "tenant" => Some(ServiceLifetime::Tenant),
+        "per-thread" => Some(ServiceLifetime::PerThread),
+        _ => None,
+    }
+}
+
+impl ServiceCollection {
+    /// Adds a named implementation of a named service that can be selected by a [binding](struct.ServiceBindings.html).
+    ///
+    /// # Arguments
+    ///
+    /// * `service_name` - The name of the service
+    /// * `name` - The name of the implementation
+    /// * `descriptor` - The [descriptor](struct.ServiceDescriptor.html) of the implementation
+    ///
+    /// # Remarks
+    ///
+    /// A named implementation is not registered until it is selected by [bind](#method.bind).
+    /// The lifetime of the descriptor is used unless the binding overrides it.
+    pub fn add_named_impl(
+        &mut self,
+        service_name: &str,
+        name: &str,
+        descriptor: ServiceDescriptor,
+    ) -> &mut Self {
+        self.named.push(NamedImplementation {
+            service_name: service_name.to_owned(),
+            name: name.to_owned(),
+            descriptor,
+        });
+        self
+    }
+
+    /// Registers the named implementations selected by the specified bindings.
+    ///
+    /// # Arguments
+    ///
+    /// * `bindings` - The [service bindings](struct.ServiceBindings.html) to apply
+    ///
+    /// # Remarks
+    ///
+    /// A selected implementation replaces the first registration of its service type, such as a
+    /// default registered in code, which also makes applying the same bindings more than once safe.
+    /// A binding to an unknown service, implementation, or lifetime is reported as a
+    /// [validation error](struct.ValidationError.html) when the service provider is built.
+    pub fn bind(&mut self, bindings: &ServiceBindings) -> &mut Self {
+        for (service_name, binding) in bindings.iter() {
+            let candidates: Vec<_> = self
+                .named
+                .iter()
+                .filter(|n| n.service_name == service_name)
+                .collect();
+
+            if candidates.is_empty() {
+                self.binding_error(format!(
+                    "The bound service '{}' does not have any named implementations",
+                    service_name
+                ));
+                continue;
+            }
+
+            let implementation = match candidates
+                .iter()
+                .find(|n| n.name == binding.implementation())
+            {
+                Some(implementation) => implementation,
+                _ => {
+                    let names: Vec<_> = candidates.iter().map(|n| n.name.as_str()).collect();
+                    let error = format!(
+                        "The service '{}' does not have an implementation named '{}'. Expected one of: {}",
+                        service_name,
+                        binding.implementation(),
+                        names.join(", ")
+                    );
+                    self.binding_error(error);
+                    continue;
+                }
+            };
+
+            let descriptor = match binding.lifetime() {
+                Some(name) => match parse_lifetime(name) {
+                    Some(lifetime) => implementation.descriptor.with_lifetime(lifetime),
+                    _ => {
+                        self.binding_error(format!(
+                            "The service '{}' is bound to an unknown lifetime '{}'",
+                            service_name, name
+                        ));
+                        continue;
+                    }
+                },
+                _ => implementation.descriptor.clone(),
+            };
+
+            self.replace(descriptor);
+        }
+
+        self
+    }
+
+    // the same bindings may be applied more than once, but each error is only reported once
+    fn binding_error(&mut self, error: String) {
+        if !self.binding_errors.contains(&error) {
+            self.binding_errors.push(error);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{test::*, *};
+
+    #[test]
+    fn bind_should_register_selected_implementation() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services
+            .add_named_impl(
+                "test",
+                "one",
+                singleton::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl { value: 1 })),
+            )
+            .add_named_impl(
+                "test",
+                "two",
+                singleton::<dyn TestService, TestService2Impl>()
+                    .from(|_| ServiceRef::new(TestService2Impl { value: 2 })),
+            );
+
+        let bindings = ServiceBindings::new().bind("test", "two");
+
+        // act
+        let provider = services.bind(&bindings).build_provider().unwrap();
+
+        // assert
+        let descriptor = provider.descriptor::<dyn TestService>().unwrap();
+        assert_eq!(provider.get_required::<dyn TestService>().value(), 2);
+        assert_eq!(descriptor.lifetime(), ServiceLifetime::Singleton);
+    }
+
+    #[test]
+    fn bind_should_register_implementation_type_and_dependencies() {
+        // arrange
+        let mut services = ServiceCollection::new();
+        let bindings = ServiceBindings::new().bind("other", "impl");
+
+        services.add_named_impl(
+            "other",
+            "impl",
+            transient::<dyn OtherTestService, OtherTestServiceImpl>()
+                .depends_on(exactly_one::<dyn TestService>())
+                .from(|sp| ServiceRef::new(OtherTestServiceImpl::new(sp.get_required()))),
+        );
+
+        // act
+        let result = services.bind(&bindings).build_provider();
+
+        // assert
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            format!(
+                "Service '{}' requires dependent service '{}', which has not be registered",
+                Type::of::<OtherTestServiceImpl>().name(),
+                Type::of::<dyn TestService>().name()
+            )
+        );
+    }
+
+    #[test]
+    fn bind_should_not_register_implementation_more_than_once() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services
+            .add_named_impl(
+                "test",
+                "one",
+                singleton::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl { value: 1 })),
+            )
+            .add_named_impl(
+                "test",
+                "two",
+                singleton::<dyn TestService, TestService2Impl>()
+                    .from(|_| ServiceRef::new(TestService2Impl { value: 2 })),
+            );
+
+        let bindings = ServiceBindings::new().bind("test", "two");
+
+        // act
+        services.bind(&bindings).bind(&bindings);
+
+        // assert
+        assert_eq!(services.len(), 1);
+    }
+
+    #[test]
+    fn bind_should_replace_default_implementation() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services
+            .add(
+                singleton::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl::default())),
+            )
+            .add_named_impl(
+                "test",
+                "two",
+                singleton::<dyn TestService, TestService2Impl>()
+                    .from(|_| ServiceRef::new(TestService2Impl { value: 2 })),
+            );
+
+        let bindings = ServiceBindings::new().bind("test", "two");
+
+        // act
+        let provider = services.bind(&bindings).build_provider().unwrap();
+
+        // assert
+        assert_eq!(services.len(), 1);
+        assert_eq!(provider.get_required::<dyn TestService>().value(), 2);
+    }
+
+    #[test]
+    fn bind_should_report_unknown_names_once() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services
+            .add_named_impl(
+                "test",
+                "one",
+                singleton::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl { value: 1 })),
+            )
+            .add_named_impl(
+                "test",
+                "two",
+                singleton::<dyn TestService, TestService2Impl>()
+                    .from(|_| ServiceRef::new(TestService2Impl { value: 2 })),
+            );
+
+        let bindings = ServiceBindings::new().bind("other", "one");
+
+        // act
+        let result = services.bind(&bindings).bind(&bindings).build_provider();
+
+        // assert
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            "The bound service 'other' does not have any named implementations"
+        );
+    }
+
+    #[test]
+    fn bind_should_override_lifetime() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services
+            .add_named_impl(
+                "test",
+                "one",
+                singleton::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl { value: 1 })),
+            )
+            .add_named_impl(
+                "test",
+                "two",
+                singleton::<dyn TestService, TestService2Impl>()
+                    .from(|_| ServiceRef::new(TestService2Impl { value: 2 })),
+            );
+
+        let bindings = ServiceBindings::new().bind(
+            "test",
+            ServiceBinding::new("one").with_lifetime("Transient"),
+        );
+
+        // act
+        let provider = services.bind(&bindings).build_provider().unwrap();
+
+        // assert
+        let descriptor = provider.descriptor::<dyn TestService>().unwrap();
+        assert_eq!(descriptor.lifetime(), ServiceLifetime::Transient);
+    }
+
+    #[test]
+    fn bind_should_report_unknown_names() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services
+            .add_named_impl(
+                "test",
+                "one",
+                singleton::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl { value: 1 })),
+            )
+            .add_named_impl(
+                "test",
+                "two",
+                singleton::<dyn TestService, TestService2Impl>()
+                    .from(|_| ServiceRef::new(TestService2Impl { value: 2 })),
+            );
+
+        let bindings = ServiceBindings::new()
+            .bind("test", "three")
+            .bind("other", "one");
+
+        // act
+        let result = services.bind(&bindings).build_provider();
+
+        // assert
+        let error = result.err().unwrap().to_string();
+        assert!(error.contains(
+            "The service 'test' does not have an implementation named 'three'. Expected one of: one, two"
+        ));
+        assert!(error.contains("The bound service 'other' does not have any named implementations"));
+    }
+
+    #[test]
+    fn bind_should_report_unknown_lifetime() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services
+            .add_named_impl(
+                "test",
+                "one",
+                singleton::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl { value: 1 })),
+            )
+            .add_named_impl(
+                "test",
+                "two",
+                singleton::<dyn TestService, TestService2Impl>()
+                    .from(|_| ServiceRef::new(TestService2Impl { value: 2 })),
+            );
+
+        let bindings = ServiceBindings::new()
+            .bind("test", ServiceBinding::new("one").with_lifetime("forever"));
+
+        // act
+        let result = services.bind(&bindings).build_provider();
+
+        // assert
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            "The service 'test' is bound to an unknown lifetime 'forever'"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "toml")]
+    fn from_toml_should_read_names_and_tables() {
+        // arrange
+        let text = r#"
+# choose the cache at deployment time
+cache = "redis" # trailing comment
+clock = { implementation = "system, utc", lifetime = "scoped" }
+
+[queue]
+implementation = "memory"
+"#;
+
+        // act
+        let bindings = ServiceBindings::from_toml(text).unwrap();
+
+        // assert
+        assert_eq!(
+            bindings,
+            ServiceBindings::new()
+                .bind("cache", "redis")
+                .bind(
+                    "clock",
+                    ServiceBinding::new("system, utc").with_lifetime("scoped")
+                )
+                .bind("queue", "memory")
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "toml")]
+    fn from_toml_should_report_invalid_binding() {
+        // arrange
+        let text = "cache = \"redis\"\nclock = { lifetime = \"scoped\" }";
+
+        // act
+        let error = ServiceBindings::from_toml(text).unwrap_err();
+
+        // assert
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn from_json_should_read_names_and_tables() {
+        // arrange
+        let text = r#"{ "cache": "redis", "clock": { "implementation": "system", "lifetime": "scoped" } }"#;
+
+        // act
+        let bindings = ServiceBindings::from_json(text).unwrap();
+
+        // assert
+        assert_eq!(
+            bindings,
+            ServiceBindings::new().bind("cache", "redis").bind(
+                "clock",
+                ServiceBinding::new("system").with_lifetime("scoped")
+            )
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "toml")]
+    fn from_file_should_read_bindings() {
+        // arrange
+        let path = std::env::temp_dir().join("bindings.toml");
+        std::fs::write(&path, "cache = \"memory\"").unwrap();
+
+        // act
+        let bindings = ServiceBindings::from_file(&path);
+
+        // assert
+        std::fs::remove_file(&path).ok();
+        assert_eq!(
+            bindings.unwrap(),
+            ServiceBindings::new().bind("cache", "memory")
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "toml")]
+    fn from_file_should_report_unsupported_format() {
+        // arrange
+        let path = new_temp_file("bindings");
+
+        // act
+        let error = ServiceBindings::from_file(&path).unwrap_err();
+
+        // assert
+        std::fs::remove_file(&path).ok();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn bindings_should_deserialize_names_and_tables() {
+        // arrange
+        let json = r#"{ "cache": "redis", "clock": { "implementation": "system", "lifetime": "scoped" } }"#;
+
+        // act
+        let bindings: ServiceBindings = serde_json::from_str(json).unwrap();
+
+        // assert
+        assert_eq!(
+            bindings,
+            ServiceBindings::new().bind("cache", "redis").bind(
+                "clock",
+                ServiceBinding::new("system").with_lifetime("scoped")
+            )
+        );
+    }
+}
@@ -1,11 +1,10 @@
 use crate::*;
 use spin::Once;
 use std::any::Any;
-use std::mem::MaybeUninit;
 
 #[inline(always)]
 fn no_op(_services: &ServiceProvider) -> ServiceRef<dyn Any> {
-    ServiceRef::new(MaybeUninit::<Box<dyn Any>>::uninit())
+    ServiceRef::new(())
 }
 
 /// Initializes a new singleton service descriptor builder.
@@ -20,6 +19,7 @@ pub fn singleton<TSvc: Any + ?Sized, TImpl: 'static>() -> ServiceDescriptorBuild
 ///
 /// * `factory` - The factory method used to create the service
 #[inline]
+#[track_caller]
 pub fn singleton_factory<T: Any + ?Sized, F>(factory: F) -> ServiceDescriptor
 where
     F: Fn(&ServiceProvider) -> ServiceRef<T> + 'static,
@@ -49,6 +49,7 @@ pub fn scoped<TSvc: Any + ?Sized, TImpl: 'static>() -> ServiceDescriptorBuilder<
 ///
 /// * `factory` - The factory method used to create the service
 #[inline]
+#[track_caller]
 pub fn scoped_factory<T: Any + ?Sized, F>(factory: F) -> ServiceDescriptor
 where
     F: Fn(&ServiceProvider) -> ServiceRef<T> + 'static,
@@ -56,6 +57,77 @@ where
     ServiceDescriptorBuilder::<T, F>::new(ServiceLifetime::Scoped, Type::of::<F>()).from(factory)
 }
 
+/// Initializes a new tenant service descriptor builder.
+#[inline]
+pub fn tenant<TSvc: Any + ?Sized, TImpl: 'static>() -> ServiceDescriptorBuilder<TSvc, TImpl> {
+    ServiceDescriptorBuilder::new(ServiceLifetime::Tenant, Type::of::<TImpl>())
+}
+
+/// Initializes a new tenant service descriptor.
+///
+/// # Arguments
+///
+/// * `factory` - The factory method used to create the service
+#[inline]
+#[track_caller]
+pub fn tenant_factory<T: Any + ?Sized, F>(factory: F) -> ServiceDescriptor
+where
+    F: Fn(&ServiceProvider) -> ServiceRef<T> + 'static,
+{
+    ServiceDescriptorBuilder::<T, F>::new(ServiceLifetime::Tenant, Type::of::<F>()).from(factory)
+}
+
+/// Initializes a new per-thread service descriptor builder.
+#[inline]
+pub fn per_thread<TSvc: Any + ?Sized, TImpl: 'static>() -> ServiceDescriptorBuilder<TSvc, TImpl> {
+    ServiceDescriptorBuilder::new(ServiceLifetime::PerThread, Type::of::<TImpl>())
+}
+
+/// Initializes a new per-thread service descriptor.
+///
+/// # Arguments
+///
+/// * `factory` - The factory method used to create the service
+#[inline]
+#[track_caller]
+pub fn per_thread_factory<T: Any + ?Sized, F>(factory: F) -> ServiceDescriptor
+where
+    F: Fn(&ServiceProvider) -> ServiceRef<T> + 'static,
+{
+    ServiceDescriptorBuilder::<T, F>::new(ServiceLifetime::PerThread, Type::of::<F>()).from(factory)
+}
+
+/// Initializes a new service descriptor builder with a custom lifetime.
+///
+/// # Arguments
+///
+/// * `strategy` - The [lifetime strategy](trait.LifetimeStrategy.html) that caches the service
+#[inline]
+pub fn custom<TSvc: Any + ?Sized, TImpl: 'static>(
+    strategy: &'static dyn LifetimeStrategy,
+) -> ServiceDescriptorBuilder<TSvc, TImpl> {
+    ServiceDescriptorBuilder::new(ServiceLifetime::Custom(strategy), Type::of::<TImpl>())
+}
+
+/// Initializes a new service descriptor with a custom lifetime.
+///
+/// # Arguments
+///
+/// * `strategy` - The [lifetime strategy](trait.LifetimeStrategy.html) that caches the service
+/// * `factory` - The factory method used to create the service
+#[inline]
+#[track_caller]
+pub fn custom_factory<T: Any + ?Sized, F>(
+    strategy: &'static dyn LifetimeStrategy,
+    factory: F,
+) -> ServiceDescriptor
+where
+    F: Fn(&ServiceProvider) -> ServiceRef<T> + 'static,
+{
+    ServiceDescriptorBuilder::<T, F>::new(ServiceLifetime::Custom(strategy), Type::of::<F>())
+        .from(factory)
+}
+
 /// Initializes a new transient service descriptor builder.
 #[inline]
 pub fn transient<TSvc: Any + ?Sized, TImpl: 'static>() -> ServiceDescriptorBuilder<TSvc, TImpl> {
@@ -68,6 +140,7 @@ pub fn transient<TSvc: Any + ?Sized, TImpl: 'static>() -> ServiceDescriptorBuild
 ///
 /// * `factory` - The factory method used to create the service
 #[inline]
+#[track_caller]
 pub fn transient_factory<T: Any + ?Sized, F>(factory: F) -> ServiceDescriptor
 where
     F: Fn(&ServiceProvider) -> ServiceRef<T> + 'static,
@@ -85,6 +158,30 @@ pub fn transient_as_self<T: Any>() -> ServiceDescriptorBuilder<T, T> {
     ServiceDescriptorBuilder::new(ServiceLifetime::Transient, Type::of::<T>())
 }
 
+/// Initializes a new service descriptor for a factory that creates a service from a runtime argument.
+///
+/// # Arguments
+///
+/// * `factory` - The factory method used to create the service
+///
+/// # Remarks
+///
+/// The registered service is a transient [ParamFactory](struct.ParamFactory.html)`<A, T>`.
+/// Multiple runtime arguments are represented as a tuple.
+#[inline]
+#[track_caller]
+pub fn param_factory<A: 'static, T: Any + ?Sized, F>(factory: F) -> ServiceDescriptor
+where
+    F: Fn(&ServiceProvider, A) -> ServiceRef<T> + 'static,
+{
+    let factory: ServiceRef<ParamServiceFactory<A, T>> = ServiceRef::new(factory);
+    ServiceDescriptorBuilder::<ParamFactory<A, T>, F>::new(
+        ServiceLifetime::Transient,
+        Type::of::<F>(),
+    )
+    .from(move |sp| ServiceRef::new(ParamFactory::new(sp.clone(), factory.clone())))
+}
+
 /// Creates a new singleton service descriptor for an existing service instance.
 ///
 /// # Arguments
@@ -95,6 +192,7 @@ pub fn transient_as_self<T: Any>() -> ServiceDescriptorBuilder<T, T> {
 ///
 /// This function maps an existing instance to a trait
 #[inline]
+#[track_caller]
 pub fn existing<TSvc: Any + ?Sized, TImpl: 'static>(instance: Box<TSvc>) -> ServiceDescriptor {
     ServiceDescriptor::new(
         ServiceLifetime::Singleton,
@@ -115,6 +213,7 @@ pub fn existing<TSvc: Any + ?Sized, TImpl: 'static>(instance: Box<TSvc>) -> Serv
 ///
 /// This function maps an existing instance to itself rather than a trait
 #[inline]
+#[track_caller]
 pub fn existing_as_self<T: Any>(instance: T) -> ServiceDescriptor {
     ServiceDescriptor::new(
         ServiceLifetime::Singleton,
@@ -141,4 +240,25 @@ pub fn zero_or_one<T: Any + ?Sized>() -> ServiceDependency {
 #[inline]
 pub fn zero_or_more<T: Any + ?Sized>() -> ServiceDependency {
     ServiceDependency::new(Type::of::<T>(), ServiceCardinality::ZeroOrMore)
-}
\ No newline at end of file
+}
+
+/// Creates a new, lazily-resolved service dependency with a cardinality of exactly one (1:1).
+#[cfg(feature = "lazy")]
+#[inline]
+pub fn lazy_exactly_one<T: Any + ?Sized>() -> ServiceDependency {
+    ServiceDependency::new_lazy(Type::of::<T>(), ServiceCardinality::ExactlyOne)
+}
+
+/// Creates a new, lazily-resolved service dependency with a cardinality of zero or one (0:1).
+#[cfg(feature = "lazy")]
+#[inline]
+pub fn lazy_zero_or_one<T: Any + ?Sized>() -> ServiceDependency {
+    ServiceDependency::new_lazy(Type::of::<T>(), ServiceCardinality::ZeroOrOne)
+}
+
+/// Creates a new, lazily-resolved service dependency with a cardinality of zero or more (0:*).
+#[cfg(feature = "lazy")]
+#[inline]
+pub fn lazy_zero_or_more<T: Any + ?Sized>() -> ServiceDependency {
+    ServiceDependency::new_lazy(Type::of::<T>(), ServiceCardinality::ZeroOrMore)
+}
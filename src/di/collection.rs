@@ -1,15 +1,26 @@
-use crate::{validate, ServiceDescriptor, ServiceProvider, Type, ValidationError};
+use crate::{
+    binding::NamedImplementation, condition::ConditionalRegistration, validate, ConditionOutcome,
+    Interceptor, Manifest, RegistrationContext, ResolutionObserver, ServiceDescriptor,
+    ServiceProvider, ServiceRef, Type, ValidationError,
+};
 use std::any::Any;
-use std::collections::HashMap;
-use std::iter::{DoubleEndedIterator, ExactSizeIterator};
 use std::ops::Index;
 use std::slice::{Iter, IterMut};
 use std::vec::IntoIter;
 
+// validates services that can only be evaluated by resolving them, such as options
+pub(crate) type ServiceValidation = dyn Fn(&ServiceProvider) -> Result<(), String>;
+
 /// Represents a service collection.
 #[derive(Default)]
 pub struct ServiceCollection {
     items: Vec<ServiceDescriptor>,
+    observers: Vec<ServiceRef<dyn ResolutionObserver>>,
+    interceptors: Vec<ServiceRef<dyn Interceptor>>,
+    pub(crate) named: Vec<NamedImplementation>,
+    pub(crate) binding_errors: Vec<String>,
+    pub(crate) conditional: Vec<ConditionalRegistration>,
+    pub(crate) validations: Vec<(Type, Box<ServiceValidation>)>,
 }
 
 impl ServiceCollection {
@@ -56,6 +67,68 @@ impl ServiceCollection {
         self
     }
 
+    /// Adds a service using the specified service descriptor if a condition is met.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicate` - The predicate that determines whether the service is registered
+    /// * `descriptor` - The [service descriptor](struct.ServiceDescriptor.html) to register.
+    ///
+    /// # Remarks
+    ///
+    /// The predicate is evaluated against the final collection when the
+    /// [service provider](struct.ServiceProvider.html) is built, not when this method is called.
+    /// Conditional registrations are evaluated in the order they were added and the services
+    /// whose condition is met are registered after all unconditional services.
+    pub fn add_if<F>(&mut self, predicate: F, descriptor: ServiceDescriptor) -> &mut Self
+    where
+        F: Fn(&RegistrationContext) -> bool + 'static,
+    {
+        self.conditional.push(ConditionalRegistration {
+            descriptor,
+            predicate: Box::new(predicate),
+        });
+        self
+    }
+
+    /// Adds a service using the specified service descriptor if a condition is met.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicate` - The predicate, such as an environment check, that determines whether the service is registered
+    /// * `descriptor` - The [service descriptor](struct.ServiceDescriptor.html) to register.
+    ///
+    /// # Remarks
+    ///
+    /// The predicate is evaluated when the [service provider](struct.ServiceProvider.html) is built,
+    /// not when this method is called.
+    pub fn add_when<F>(&mut self, predicate: F, descriptor: ServiceDescriptor) -> &mut Self
+    where
+        F: Fn() -> bool + 'static,
+    {
+        self.add_if(move |_| predicate(), descriptor)
+    }
+
+    /// Adds an observer that is notified when services are resolved by the built provider and its scopes.
+    ///
+    /// # Arguments
+    ///
+    /// * `observer` - The [resolution observer](trait.ResolutionObserver.html) to add.
+    pub fn add_observer<T: ResolutionObserver + 'static>(&mut self, observer: T) -> &mut Self {
+        self.observers.push(ServiceRef::new(observer));
+        self
+    }
+
+    /// Adds an interceptor that participates in resolving services from the built provider and its scopes.
+    ///
+    /// # Arguments
+    ///
+    /// * `interceptor` - The [interceptor](trait.Interceptor.html) to add.
+    pub fn add_interceptor<T: Interceptor + 'static>(&mut self, interceptor: T) -> &mut Self {
+        self.interceptors.push(ServiceRef::new(interceptor));
+        self
+    }
+
     /// Adds a service using the specified service descriptor if the service has not already been registered.
     ///
     /// # Arguments
@@ -168,34 +241,175 @@ impl ServiceCollection {
 
     /// Builds and returns a new [service provider](struct.ServiceProvider.html).
     pub fn build_provider(&self) -> Result<ServiceProvider, ValidationError> {
-        if let Err(error) = validate(self) {
-            Err(error)
+        self.build_provider_with(Vec::with_capacity(0))
+    }
+
+    pub(crate) fn build_provider_with(
+        &self,
+        observers: Vec<ServiceRef<dyn ResolutionObserver>>,
+    ) -> Result<ServiceProvider, ValidationError> {
+        let (services, conditions) = self.resolve(Vec::with_capacity(0))?;
+
+        self.validate_services(&services, None)?;
+
+        Ok(ServiceProvider::with_pipeline(
+            services,
+            self.observers.iter().cloned().chain(observers).collect(),
+            self.interceptors.clone(),
+            conditions,
+            None,
+        ))
+    }
+
+    pub(crate) fn build_child_provider(
+        &self,
+        parent: &ServiceProvider,
+    ) -> Result<ServiceProvider, ValidationError> {
+        let inherited = parent
+            .descriptors()
+            .enumerate()
+            .filter(|(_, descriptor)| {
+                !self
+                    .items
+                    .iter()
+                    .any(|item| item.service_type() == descriptor.service_type())
+            })
+            .map(|(slot, descriptor)| descriptor.inherit(slot))
+            .collect();
+        let (services, conditions) = self.resolve(inherited)?;
+
+        self.validate_services(&services, Some(parent))?;
+
+        Ok(ServiceProvider::with_pipeline(
+            services,
+            self.observers.clone(),
+            self.interceptors.clone(),
+            conditions,
+            Some(parent.clone()),
+        ))
+    }
+
+    // the services are resolved from a provider that is discarded afterward so that the
+    // validations do not notify the observers or interceptors added to the collection
+    fn validate_services(
+        &self,
+        services: &[ServiceDescriptor],
+        parent: Option<&ServiceProvider>,
+    ) -> Result<(), ValidationError> {
+        if self.validations.is_empty() {
+            return Ok(());
+        }
+
+        let provider = ServiceProvider::with_pipeline(
+            services.iter().map(|item| item.clone_with(false)).collect(),
+            Vec::with_capacity(0),
+            Vec::with_capacity(0),
+            Vec::with_capacity(0),
+            parent.cloned(),
+        );
+        let errors: Vec<_> = self
+            .validations
+            .iter()
+            .filter_map(|(_, validate)| validate(&provider).err())
+            .collect();
+
+        if errors.is_empty() {
+            Ok(())
         } else {
-            let mut services = HashMap::with_capacity(self.items.len());
+            Err(ValidationError::new(errors))
+        }
+    }
 
-            for item in &self.items {
-                let key = item.service_type().clone();
-                let descriptors = services.entry(key).or_insert_with(Vec::new);
+    // resolves and validates the final set of services, which are the inherited services
+    // that are not replaced followed by the registered and conditionally registered services
+    fn resolve(
+        &self,
+        mut inherited: Vec<ServiceDescriptor>,
+    ) -> Result<(Vec<ServiceDescriptor>, Vec<ConditionOutcome>), ValidationError> {
+        let (accepted, conditions) = self.evaluate_conditions(&inherited);
+        let resolved;
+        let services = if accepted.is_empty() && inherited.is_empty() {
+            self
+        } else {
+            inherited.retain(|descriptor| {
+                !accepted
+                    .iter()
+                    .any(|item| item.service_type() == descriptor.service_type())
+            });
+            resolved = Self {
+                items: inherited
+                    .into_iter()
+                    .chain(self.items.iter().chain(accepted).cloned())
+                    .collect(),
+                binding_errors: self.binding_errors.clone(),
+                ..Default::default()
+            };
+            &resolved
+        };
+
+        validate(services)?;
+
+        // note: dependencies are only interesting for validation. they are retained after a
+        // ServiceProvider is created so that a child provider can validate its registrations
+        // against the services it inherits.
+        let services = services
+            .items
+            .iter()
+            .map(|item| item.clone_with(true))
+            .collect();
+
+        Ok((services, conditions))
+    }
+
+    /// Evaluates the conditional registrations against the collection and returns their outcomes.
+    ///
+    /// # Remarks
+    ///
+    /// The outcomes are the same as those recorded by a [service provider](struct.ServiceProvider.html)
+    /// built from the collection in its current state.
+    pub fn conditions(&self) -> Vec<ConditionOutcome> {
+        self.evaluate_conditions(&[]).1
+    }
+
+    pub(crate) fn evaluate_conditions<'a>(
+        &'a self,
+        inherited: &[ServiceDescriptor],
+    ) -> (Vec<&'a ServiceDescriptor>, Vec<ConditionOutcome>) {
+        let mut accepted = Vec::new();
+        let mut outcomes = Vec::with_capacity(self.conditional.len());
+
+        for registration in &self.conditional {
+            let context = RegistrationContext::new(
+                inherited
+                    .iter()
+                    .chain(&self.items)
+                    .chain(accepted.iter().copied()),
+            );
+            let registered = (registration.predicate)(&context);
 
-                // note: dependencies are only interesting for validation. after a ServiceProvider
-                // is created, no further validation occurs. prevent copying unnecessary memory
-                // and allow it to potentially be freed if the ServiceCollection is dropped.
-                descriptors.push(item.clone_with(false));
-            }
+            outcomes.push(ConditionOutcome::new(&registration.descriptor, registered));
 
-            for values in services.values_mut() {
-                values.shrink_to_fit();
+            if registered {
+                accepted.push(&registration.descriptor);
             }
-
-            services.shrink_to_fit();
-            Ok(ServiceProvider::new(services))
         }
+
+        (accepted, outcomes)
+    }
+
+    /// Creates and returns a [manifest](struct.Manifest.html) of the services in the collection.
+    ///
+    /// # Remarks
+    ///
+    /// The manifest contains the same services as a [service provider](struct.ServiceProvider.html)
+    /// built from the collection in its current state, including the conditional registrations
+    /// whose conditions are met.
+    pub fn manifest(&self) -> Manifest {
+        Manifest::from(self)
     }
 
     /// Gets a read-only iterator for the collection
-    pub fn iter(
-        &self,
-    ) -> impl Iterator<Item = &ServiceDescriptor> + ExactSizeIterator + DoubleEndedIterator {
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &ServiceDescriptor> + DoubleEndedIterator {
         self.items.iter()
     }
 }
@@ -390,7 +604,7 @@ mod tests {
         ];
         let mut collection = ServiceCollection::new();
 
-        collection.try_add_all(descriptors.into_iter());
+        collection.try_add_all(descriptors);
 
         // act
         let count = collection.len();
@@ -524,4 +738,75 @@ mod tests {
         remove_file(&file).ok();
         assert!(not_dropped);
     }
+
+    #[test]
+    fn add_if_should_evaluate_predicate_against_final_collection() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services.add_if(
+            |ctx| ctx.is_registered::<dyn TestService>(),
+            transient::<dyn OtherTestService, OtherTestServiceImpl>().from(|sp| {
+                ServiceRef::new(OtherTestServiceImpl::new(
+                    sp.get_required::<dyn TestService>(),
+                ))
+            }),
+        );
+        services.add(
+            singleton::<dyn TestService, TestServiceImpl>()
+                .from(|_| ServiceRef::new(TestServiceImpl::default())),
+        );
+
+        // act
+        let provider = services.build_provider().unwrap();
+
+        // assert
+        assert!(provider.get::<dyn OtherTestService>().is_some());
+        assert!(provider.conditions()[0].is_registered());
+    }
+
+    #[test]
+    fn add_when_should_record_skipped_registration() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services.add_when(
+            || false,
+            singleton::<dyn TestService, TestServiceImpl>()
+                .from(|_| ServiceRef::new(TestServiceImpl::default())),
+        );
+
+        // act
+        let provider = services.build_provider().unwrap();
+
+        // assert
+        let outcome = &provider.conditions()[0];
+        assert!(provider.get::<dyn TestService>().is_none());
+        assert!(!outcome.is_registered());
+        assert_eq!(outcome.service_type(), &Type::of::<dyn TestService>());
+        assert!(outcome.origin().file().ends_with("collection.rs"));
+    }
+
+    #[test]
+    fn build_provider_should_validate_registered_conditional_services() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services.add_when(
+            || true,
+            transient::<dyn OtherTestService, OtherTestServiceImpl>()
+                .depends_on(exactly_one::<dyn TestService>())
+                .from(|sp| {
+                    ServiceRef::new(OtherTestServiceImpl::new(
+                        sp.get_required::<dyn TestService>(),
+                    ))
+                }),
+        );
+
+        // act
+        let result = services.build_provider();
+
+        // assert
+        assert!(result.is_err());
+    }
 }
@@ -0,0 +1,95 @@
+use crate::{ServiceDescriptor, Type};
+use std::any::Any;
+use std::panic::Location;
+
+pub(crate) type Predicate = Box<dyn Fn(&RegistrationContext) -> bool>;
+
+pub(crate) struct ConditionalRegistration {
+    pub(crate) descriptor: ServiceDescriptor,
+    pub(crate) predicate: Predicate,
+}
+
+/// Represents the context used to evaluate a conditional registration.
+///
+/// # Remarks
+///
+/// The context contains every unconditional registration in the final
+/// [service collection](struct.ServiceCollection.html) as well as any conditional
+/// registrations that were evaluated and registered before the current one.
+pub struct RegistrationContext<'a> {
+    descriptors: Vec<&'a ServiceDescriptor>,
+}
+
+impl<'a> RegistrationContext<'a> {
+    pub(crate) fn new(descriptors: impl Iterator<Item = &'a ServiceDescriptor>) -> Self {
+        Self {
+            descriptors: descriptors.collect(),
+        }
+    }
+
+    /// Returns true if a service of the specified type is registered.
+    pub fn is_registered<T: Any + ?Sized>(&self) -> bool {
+        self.is_registered_by_type(&Type::of::<T>())
+    }
+
+    /// Returns true if a service of the specified type is registered.
+    ///
+    /// # Arguments
+    ///
+    /// * `service_type` - The [type](struct.Type.html) of service
+    pub fn is_registered_by_type(&self, service_type: &Type) -> bool {
+        self.descriptors
+            .iter()
+            .any(|d| d.service_type() == service_type)
+    }
+
+    /// Gets the number of registrations of the specified service type.
+    pub fn count<T: Any + ?Sized>(&self) -> usize {
+        let key = Type::of::<T>();
+        self.descriptors
+            .iter()
+            .filter(|d| d.service_type() == key)
+            .count()
+    }
+}
+
+/// Represents the outcome of evaluating a conditional registration.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConditionOutcome {
+    service_type: Type,
+    implementation_type: Type,
+    origin: &'static Location<'static>,
+    registered: bool,
+}
+
+impl ConditionOutcome {
+    pub(crate) fn new(descriptor: &ServiceDescriptor, registered: bool) -> Self {
+        Self {
+            service_type: descriptor.service_type().clone(),
+            implementation_type: descriptor.implementation_type().clone(),
+            origin: descriptor.origin(),
+            registered,
+        }
+    }
+
+    /// Gets the [type](struct.Type.html) of the conditional service.
+    pub fn service_type(&self) -> &Type {
+        &self.service_type
+    }
+
+    /// Gets the [type](struct.Type.html) of the conditional service implementation.
+    pub fn implementation_type(&self) -> &Type {
+        &self.implementation_type
+    }
+
+    /// Gets the source code location where the conditional service was defined.
+    pub fn origin(&self) -> &'static Location<'static> {
+        self.origin
+    }
+
+    /// Returns true if the condition was met and the service was registered;
+    /// otherwise, false if the service was skipped.
+    pub fn is_registered(&self) -> bool {
+        self.registered
+    }
+}
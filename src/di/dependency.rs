@@ -18,6 +18,7 @@ pub enum ServiceCardinality {
 pub struct ServiceDependency {
     injected_type: Type,
     cardinality: ServiceCardinality,
+    lazy: bool,
 }
 
 impl ServiceDependency {
@@ -31,6 +32,26 @@ impl ServiceDependency {
         Self {
             injected_type,
             cardinality,
+            lazy: false,
+        }
+    }
+
+    /// Initializes a new, lazily-resolved service dependency.
+    ///
+    /// # Arguments
+    ///
+    /// * `injected_type` - the [injected type](struct.Type.html) of the service dependency
+    /// * `cardinality` - the [cardinality](enum.ServiceCardinality.html) of the service dependency
+    ///
+    /// # Remarks
+    ///
+    /// A lazy dependency is not resolved when the dependent service is created and,
+    /// therefore, does not contribute to a circular dependency.
+    pub fn new_lazy(injected_type: Type, cardinality: ServiceCardinality) -> Self {
+        Self {
+            injected_type,
+            cardinality,
+            lazy: true,
         }
     }
 
@@ -43,4 +64,9 @@ impl ServiceDependency {
     pub fn cardinality(&self) -> ServiceCardinality {
         self.cardinality
     }
+
+    /// Gets a value indicating whether the service dependency is resolved lazily.
+    pub fn is_lazy(&self) -> bool {
+        self.lazy
+    }
 }
\ No newline at end of file
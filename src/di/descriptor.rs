@@ -1,10 +1,29 @@
-use crate::{ServiceDependency, ServiceProvider, Type};
+use crate::{
+    provider::Instance, thread, LifetimeStrategy, ServiceDependency, ServiceProvider, Type,
+};
 use spin::Once;
 use std::any::Any;
+use std::cell::Cell;
+use std::fmt::{Debug, Formatter};
 use std::marker::PhantomData;
+use std::panic::Location;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+static NEXT_CACHE_KEY: AtomicUsize = AtomicUsize::new(1);
+
+fn next_cache_key() -> usize {
+    NEXT_CACHE_KEY.fetch_add(1, Ordering::Relaxed)
+}
 
 /// Represents the possible service lifetimes.
-#[derive(Copy, Clone, Debug, PartialEq)]
+///
+/// # Remarks
+///
+/// New lifetimes may be added in the future; therefore, a `match` on a service lifetime
+/// must include a wildcard arm.
+#[derive(Copy, Clone)]
+#[non_exhaustive]
 pub enum ServiceLifetime {
     /// Indicates that a single instance of the service will be created.
     Singleton,
@@ -14,6 +33,79 @@ pub enum ServiceLifetime {
 
     /// Indicates that a new instance of the service will be created every time it is requested.
     Transient,
+
+    /// Indicates that a new instance of the service will be created for each tenant.
+    Tenant,
+
+    /// Indicates that a new instance of the service will be created for each thread.
+    ///
+    /// The instances created on the thread that drops the service provider are dropped with the
+    /// provider. The instances created on any other thread are not dropped until that thread
+    /// creates another per-thread instance or exits.
+    PerThread,
+
+    /// Indicates that the instances of the service are cached by a custom [lifetime strategy](trait.LifetimeStrategy.html).
+    Custom(&'static dyn LifetimeStrategy),
+}
+
+impl ServiceLifetime {
+    /// Gets the name of the lifetime.
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Singleton => "singleton",
+            Self::Scoped => "scoped",
+            Self::Transient => "transient",
+            Self::Tenant => "tenant",
+            Self::PerThread => "per-thread",
+            Self::Custom(strategy) => strategy.name(),
+        }
+    }
+
+    /// Gets the width of the lifetime relative to other lifetimes.
+    ///
+    /// # Remarks
+    ///
+    /// A service must not depend on a service whose lifetime has a smaller, non-zero width.
+    /// A per-thread lifetime has no width because its instances are neither longer nor shorter
+    /// lived than scoped or tenant instances. A per-thread service must not be captured by a
+    /// service with a non-zero width and must not capture a service other than a transient or
+    /// singleton service.
+    pub fn width(&self) -> usize {
+        match self {
+            Self::Transient | Self::PerThread => 0,
+            Self::Scoped => 100,
+            Self::Tenant => 200,
+            Self::Singleton => 300,
+            Self::Custom(strategy) => strategy.width(),
+        }
+    }
+}
+
+impl PartialEq for ServiceLifetime {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            // custom lifetimes are equal only when they use the same strategy. zero-sized
+            // strategies of different types can share an address so the types must match too
+            (Self::Custom(a), Self::Custom(b)) => {
+                std::ptr::eq(*a as *const _ as *const u8, *b as *const _ as *const u8)
+                    && Any::type_id(*a) == Any::type_id(*b)
+            }
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
+}
+
+impl Debug for ServiceLifetime {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Singleton => f.write_str("Singleton"),
+            Self::Scoped => f.write_str("Scoped"),
+            Self::Transient => f.write_str("Transient"),
+            Self::Tenant => f.write_str("Tenant"),
+            Self::PerThread => f.write_str("PerThread"),
+            Self::Custom(strategy) => f.debug_tuple("Custom").field(&strategy.name()).finish(),
+        }
+    }
 }
 
 /// Represents the type alias for a service reference.
@@ -24,6 +116,30 @@ pub type ServiceRef<T> = std::rc::Rc<T>;
 #[cfg(feature = "async")]
 pub type ServiceRef<T> = std::sync::Arc<T>;
 
+/// Represents the thread safety required of an extension shared by a service provider and its scopes.
+///
+/// # Remarks
+///
+/// When the **async** feature is enabled, an extension must be `Send` and `Sync`;
+/// otherwise, there are no additional requirements.
+#[cfg(not(feature = "async"))]
+pub trait Shareable {}
+
+#[cfg(not(feature = "async"))]
+impl<T: ?Sized> Shareable for T {}
+
+/// Represents the thread safety required of an extension shared by a service provider and its scopes.
+///
+/// # Remarks
+///
+/// When the **async** feature is enabled, an extension must be `Send` and `Sync`;
+/// otherwise, there are no additional requirements.
+#[cfg(feature = "async")]
+pub trait Shareable: Send + Sync {}
+
+#[cfg(feature = "async")]
+impl<T: Send + Sync + ?Sized> Shareable for T {}
+
 /// Represents the callback function used to create a service.
 pub type ServiceFactory = dyn Fn(&ServiceProvider) -> ServiceRef<dyn Any>;
 
@@ -33,12 +149,22 @@ pub struct ServiceDescriptor {
     service_type: Type,
     implementation_type: Type,
     dependencies: Vec<ServiceDependency>,
-    instance: ServiceRef<Once<ServiceRef<dyn Any>>>,
+    instance: ServiceRef<Instance>,
     factory: ServiceRef<ServiceFactory>,
+    origin: &'static Location<'static>,
+    cache_key: usize,
+    cache_slot: Option<usize>,
+    order: i32,
+    before: Vec<Type>,
+    after: Vec<Type>,
+    tags: Vec<String>,
+    metadata: Vec<ServiceRef<dyn Any>>,
+    inherited: bool,
 }
 
 impl ServiceDescriptor {
     #[cfg(feature = "builder")]
+    #[track_caller]
     pub(crate) fn new(
         lifetime: ServiceLifetime,
         service_type: Type,
@@ -51,8 +177,17 @@ impl ServiceDescriptor {
             service_type,
             implementation_type,
             dependencies: Vec::with_capacity(0),
-            instance: ServiceRef::new(instance),
+            instance: ServiceRef::new(Instance::new(instance)),
             factory,
+            origin: Location::caller(),
+            cache_key: next_cache_key(),
+            cache_slot: None,
+            order: 0,
+            before: Vec::with_capacity(0),
+            after: Vec::with_capacity(0),
+            tags: Vec::with_capacity(0),
+            metadata: Vec::with_capacity(0),
+            inherited: false,
         }
     }
 
@@ -76,17 +211,183 @@ impl ServiceDescriptor {
         &self.dependencies
     }
 
+    /// Gets the source code location where the service descriptor was defined.
+    pub fn origin(&self) -> &'static Location<'static> {
+        self.origin
+    }
+
+    /// Gets the order of the service relative to other services of the same service type.
+    pub fn order(&self) -> i32 {
+        self.order
+    }
+
+    /// Gets the implementation types that the service must precede.
+    pub fn before(&self) -> &[Type] {
+        &self.before
+    }
+
+    /// Gets the implementation types that the service must follow.
+    pub fn after(&self) -> &[Type] {
+        &self.after
+    }
+
+    /// Gets the tags associated with the service descriptor.
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    /// Returns true if the service descriptor has the specified tag.
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - The tag to find
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+
+    /// Gets the metadata of the specified type associated with the service descriptor, if any.
+    pub fn metadata<M: Any>(&self) -> Option<&M> {
+        self.metadata
+            .iter()
+            .rev()
+            .find_map(|metadata| metadata.downcast_ref::<M>())
+    }
+
+    /// Gets a key that identifies the cached instances of the service descriptor.
+    ///
+    /// # Remarks
+    ///
+    /// The key is unique to the service descriptor within a [service provider](struct.ServiceProvider.html)
+    /// and can be used by a [lifetime strategy](trait.LifetimeStrategy.html) to cache instances. Keys are
+    /// assigned from an increasing sequence and are never reused within a process, even after the
+    /// service provider that owns the descriptor is dropped.
+    pub fn cache_key(&self) -> usize {
+        self.cache_key
+    }
+
     /// Gets or creates the service defined by the service descriptor.
     ///
     /// # Arguments
     ///
     /// * `services` - The current [service provider](struct.ServiceProvider.html).
     pub fn get(&self, services: &ServiceProvider) -> ServiceRef<dyn Any> {
-        if self.lifetime == ServiceLifetime::Transient {
-            return (self.factory)(services);
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!(
+            "resolve",
+            service = %self.service_type,
+            implementation = %self.implementation_type,
+            lifetime = ?self.lifetime,
+            scope = services.scope_id(),
+        )
+        .entered();
+
+        if !services.is_observed(self) {
+            let create = || services.post_process(self, (self.factory)(services));
+
+            return match self.lifetime {
+                ServiceLifetime::Transient => create(),
+                ServiceLifetime::PerThread => {
+                    thread::get_or_create(self.cache_key, &self.instance, create)
+                }
+                ServiceLifetime::Custom(strategy) => strategy.get(self, services, &create),
+                _ => self.cache(services).call_once(create).clone(),
+            };
+        }
+
+        let scope = services.scope_id();
+        let created = Cell::new(false);
+        let create = || {
+            created.set(true);
+            self.create(services, scope)
+        };
+
+        services.notify_for(self, |observer| observer.on_resolving(self, scope));
+
+        let service = match self.lifetime {
+            ServiceLifetime::Transient => create(),
+            ServiceLifetime::PerThread => {
+                thread::get_or_create(self.cache_key, &self.instance, create)
+            }
+            ServiceLifetime::Custom(strategy) => strategy.get(self, services, &create),
+            _ => self.cache(services).call_once(create).clone(),
+        };
+
+        if !created.get() {
+            services.notify_for(self, |observer| observer.on_cache_hit(self, scope));
         }
 
-        return self.instance.call_once(|| (self.factory)(services)).clone();
+        services.notify_for(self, |observer| observer.on_resolved(self, scope));
+        service
+    }
+
+    pub(crate) fn is_inherited(&self) -> bool {
+        self.inherited
+    }
+
+    pub(crate) fn set_cache_slot(&mut self, slot: usize) {
+        self.cache_slot = Some(slot);
+    }
+
+    // a scoped instance is cached by the scope that resolves it and a tenant instance is
+    // cached by the tenant of the scope. a tenant instance cannot be resolved outside of a tenant.
+    fn cache<'a>(&'a self, services: &'a ServiceProvider) -> &'a Instance {
+        if let Some(slot) = self.cache_slot {
+            if self.lifetime == ServiceLifetime::Tenant {
+                return services.tenant_instance(slot).unwrap_or_else(|| {
+                    panic!(
+                        "Service type '{}' has a tenant lifetime and must be resolved from a tenant provider.",
+                        self.service_type
+                    )
+                });
+            }
+
+            if let Some(instance) = services.scoped_instance(slot) {
+                return instance;
+            }
+        }
+
+        &self.instance
+    }
+
+    fn create(&self, services: &ServiceProvider, scope: usize) -> ServiceRef<dyn Any> {
+        let start = Instant::now();
+        let service = services.post_process(self, (self.factory)(services));
+        let elapsed = start.elapsed();
+
+        services.notify_for(self, |observer| observer.on_created(self, scope, elapsed));
+        service
+    }
+
+    // an inherited descriptor resolves the service from the parent of a child provider
+    pub(crate) fn inherit(&self, slot: usize) -> Self {
+        Self {
+            lifetime: self.lifetime,
+            service_type: self.service_type.clone(),
+            implementation_type: self.implementation_type.clone(),
+            dependencies: self.dependencies.clone(),
+            instance: ServiceRef::new(Instance::default()),
+            factory: ServiceRef::new(move |sp| sp.resolve_inherited(slot)),
+            origin: self.origin,
+            cache_key: next_cache_key(),
+            cache_slot: None,
+            order: self.order,
+            before: self.before.clone(),
+            after: self.after.clone(),
+            tags: self.tags.clone(),
+            metadata: self.metadata.clone(),
+            inherited: true,
+        }
+    }
+
+    // a descriptor with a different lifetime never shares the instance of the original descriptor
+    pub(crate) fn with_lifetime(&self, lifetime: ServiceLifetime) -> Self {
+        Self {
+            lifetime,
+            instance: ServiceRef::new(Instance::default()),
+            cache_key: next_cache_key(),
+            cache_slot: None,
+            ..self.clone_with(true)
+        }
     }
 
     pub(crate) fn clone_with(&self, dependencies: bool) -> Self {
@@ -102,9 +403,22 @@ impl ServiceDescriptor {
             instance: if self.lifetime == ServiceLifetime::Singleton {
                 self.instance.clone()
             } else {
-                ServiceRef::new(Once::new())
+                ServiceRef::new(Instance::default())
             },
             factory: self.factory.clone(),
+            origin: self.origin,
+            cache_key: if self.lifetime == ServiceLifetime::Singleton {
+                self.cache_key
+            } else {
+                next_cache_key()
+            },
+            cache_slot: self.cache_slot,
+            order: self.order,
+            before: self.before.clone(),
+            after: self.after.clone(),
+            tags: self.tags.clone(),
+            metadata: self.metadata.clone(),
+            inherited: self.inherited,
         }
     }
 }
@@ -122,6 +436,11 @@ pub struct ServiceDescriptorBuilder<TSvc: Any + ?Sized, TImpl> {
     lifetime: ServiceLifetime,
     implementation_type: Type,
     dependencies: Vec<ServiceDependency>,
+    order: i32,
+    before: Vec<Type>,
+    after: Vec<Type>,
+    tags: Vec<String>,
+    metadata: Vec<ServiceRef<dyn Any>>,
     _marker_svc: PhantomData<TSvc>,
     _marker_impl: PhantomData<TImpl>,
 }
@@ -132,6 +451,13 @@ impl<TSvc: Any + ?Sized, TImpl> ServiceDescriptorBuilder<TSvc, TImpl> {
     /// # Arguments
     ///
     /// * `factory` - The factory method used to create the service
+    ///
+    /// # Remarks
+    ///
+    /// The service type can be unsized, such as a trait object. The [ServiceRef](type.ServiceRef.html)
+    /// returned by the factory is therefore wrapped in a second, type-erased reference so that the
+    /// provider can cache and downcast it.
+    #[track_caller]
     pub fn from<F>(mut self, factory: F) -> ServiceDescriptor
     where
         F: Fn(&ServiceProvider) -> ServiceRef<TSvc> + 'static,
@@ -146,8 +472,17 @@ impl<TSvc: Any + ?Sized, TImpl> ServiceDescriptorBuilder<TSvc, TImpl> {
                 self.dependencies.shrink_to_fit();
                 self.dependencies
             },
-            instance: ServiceRef::new(Once::new()),
+            instance: ServiceRef::new(Instance::default()),
             factory: ServiceRef::new(move |sp| ServiceRef::new(factory(sp))),
+            origin: Location::caller(),
+            cache_key: next_cache_key(),
+            cache_slot: None,
+            order: self.order,
+            before: self.before,
+            after: self.after,
+            tags: self.tags,
+            metadata: self.metadata,
+            inherited: false,
         }
     }
 
@@ -161,6 +496,67 @@ impl<TSvc: Any + ?Sized, TImpl> ServiceDescriptorBuilder<TSvc, TImpl> {
         self
     }
 
+    /// Defines the order of the service relative to other services of the same service type.
+    ///
+    /// # Arguments
+    ///
+    /// * `order` - The order of the service. Services with a lower order are resolved first.
+    ///
+    /// # Remarks
+    ///
+    /// Services with the same order are resolved in the order they were registered. The order only
+    /// applies when all of the services of a type are resolved; a single service is always resolved
+    /// from the last registration.
+    pub fn order(mut self, order: i32) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Defines an implementation of the same service type that the service must precede.
+    ///
+    /// # Remarks
+    ///
+    /// Relative constraints take precedence over the [order](#method.order) of a service.
+    pub fn before<T: Any + ?Sized>(mut self) -> Self {
+        self.before.push(Type::of::<T>());
+        self
+    }
+
+    /// Defines an implementation of the same service type that the service must follow.
+    ///
+    /// # Remarks
+    ///
+    /// Relative constraints take precedence over the [order](#method.order) of a service.
+    pub fn after<T: Any + ?Sized>(mut self) -> Self {
+        self.after.push(Type::of::<T>());
+        self
+    }
+
+    /// Defines a tag associated with the service.
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - The tag used to discover the service
+    pub fn with_tag(mut self, tag: &str) -> Self {
+        self.tags.push(tag.to_owned());
+        self
+    }
+
+    /// Defines metadata associated with the service.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The metadata, such as a route, associated with the service
+    ///
+    /// # Remarks
+    ///
+    /// Metadata is identified by its type. If metadata of the same type is defined
+    /// more than once, the last value is used.
+    pub fn with_metadata<M: Any>(mut self, value: M) -> Self {
+        self.metadata.push(ServiceRef::new(value));
+        self
+    }
+
     /// Initializes a new service descriptor builder.
     ///
     /// # Arguments
@@ -171,6 +567,11 @@ impl<TSvc: Any + ?Sized, TImpl> ServiceDescriptorBuilder<TSvc, TImpl> {
             lifetime,
             implementation_type,
             dependencies: Vec::new(),
+            order: 0,
+            before: Vec::with_capacity(0),
+            after: Vec::with_capacity(0),
+            tags: Vec::with_capacity(0),
+            metadata: Vec::with_capacity(0),
             _marker_svc: PhantomData,
             _marker_impl: PhantomData,
         }
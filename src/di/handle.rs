@@ -0,0 +1,173 @@
+use crate::{provider::Registry, ServiceProvider, ServiceRef};
+use std::any::{type_name, Any};
+use std::marker::PhantomData;
+
+/// Represents a handle to a registered service that resolves the service without looking up its type.
+///
+/// # Remarks
+///
+/// A service handle is obtained once from [ServiceProvider::handle](struct.ServiceProvider.html#method.handle)
+/// and can be used to resolve the service from the provider that created it or any of its scopes.
+/// A handle only caches the registration of the service; the service is otherwise resolved exactly
+/// as it is by [ServiceProvider::get](struct.ServiceProvider.html#method.get), including its
+/// interceptors, observers, and the allocation of any instance created by its factory. In particular,
+/// a handle does not avoid the type-erased reference that wraps each instance created by a
+/// [factory](struct.ServiceDescriptorBuilder.html#method.from).
+pub struct ServiceHandle<T: Any + ?Sized> {
+    registry: ServiceRef<Registry>,
+    slot: usize,
+    _marker: PhantomData<fn() -> ServiceRef<T>>,
+}
+
+#[cfg(feature = "async")]
+unsafe impl<T: Any + ?Sized> Send for ServiceHandle<T> {}
+
+#[cfg(feature = "async")]
+unsafe impl<T: Any + ?Sized> Sync for ServiceHandle<T> {}
+
+impl<T: Any + ?Sized> ServiceHandle<T> {
+    pub(crate) fn new(registry: ServiceRef<Registry>, slot: usize) -> Self {
+        Self {
+            registry,
+            slot,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Gets the service using the specified provider.
+    ///
+    /// # Arguments
+    ///
+    /// * `services` - The [service provider](struct.ServiceProvider.html) used to resolve the service
+    ///
+    /// # Panics
+    ///
+    /// The service provider is not the provider that created the handle or one of its scopes.
+    pub fn get(&self, services: &ServiceProvider) -> Option<ServiceRef<T>> {
+        services
+            .resolve_slot(&self.registry, self.slot)
+            .map(|service| service.downcast_ref::<ServiceRef<T>>().unwrap().clone())
+    }
+
+    /// Gets the required service using the specified provider.
+    ///
+    /// # Arguments
+    ///
+    /// * `services` - The [service provider](struct.ServiceProvider.html) used to resolve the service
+    ///
+    /// # Panics
+    ///
+    /// The service provider is not the provider that created the handle or one of its scopes,
+    /// the service has a [tenant lifetime](enum.ServiceLifetime.html#variant.Tenant) and the
+    /// service provider is not a [tenant provider](struct.TenantProvider.html), or resolution was
+    /// denied by an [interceptor](trait.Interceptor.html).
+    pub fn get_required(&self, services: &ServiceProvider) -> ServiceRef<T> {
+        if let Some(service) = self.get(services) {
+            service
+        } else if services.requires_tenant(self.registry.descriptor(self.slot)) {
+            panic!(
+                "Service type '{}' has a tenant lifetime and must be resolved from a tenant provider.",
+                type_name::<T>()
+            );
+        } else {
+            panic!(
+                "Resolution of service type '{}' was denied.",
+                type_name::<T>()
+            );
+        }
+    }
+}
+
+impl<T: Any + ?Sized> Clone for ServiceHandle<T> {
+    fn clone(&self) -> Self {
+        Self::new(self.registry.clone(), self.slot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{test::*, *};
+
+    #[test]
+    #[allow(ambiguous_wide_pointer_comparisons)]
+    fn handle_should_resolve_service() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add(
+                singleton::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl { value: 42 })),
+            )
+            .build_provider()
+            .unwrap();
+        let handle = provider.handle::<dyn TestService>().unwrap();
+
+        // act
+        let service1 = handle.get_required(&provider);
+        let service2 = provider.get_required::<dyn TestService>();
+
+        // assert
+        assert_eq!(service1.value(), 42);
+        assert!(ServiceRef::ptr_eq(&service1, &service2));
+    }
+
+    #[test]
+    #[allow(ambiguous_wide_pointer_comparisons)]
+    fn handle_should_resolve_scoped_service_from_scope() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add(
+                scoped::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl::default())),
+            )
+            .build_provider()
+            .unwrap();
+        let handle = provider.handle::<dyn TestService>().unwrap();
+        let scope = provider.create_scope();
+
+        // act
+        let service1 = handle.get_required(&scope);
+        let service2 = handle.get_required(&provider);
+
+        // assert
+        assert!(ServiceRef::ptr_eq(
+            &service1,
+            &scope.get_required::<dyn TestService>()
+        ));
+        assert!(!ServiceRef::ptr_eq(&service1, &service2));
+    }
+
+    #[test]
+    fn handle_should_be_none_when_service_is_unregistered() {
+        // arrange
+        let provider = ServiceCollection::new().build_provider().unwrap();
+
+        // act
+        let handle = provider.handle::<dyn TestService>();
+
+        // assert
+        assert!(handle.is_none());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "The service handle was not created by this service provider or one of its scopes."
+    )]
+    fn handle_should_panic_when_used_with_another_provider() {
+        // arrange
+        let provider1 = ServiceCollection::new()
+            .add(
+                transient::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl::default())),
+            )
+            .build_provider()
+            .unwrap();
+        let provider2 = ServiceCollection::new().build_provider().unwrap();
+        let handle = provider1.handle::<dyn TestService>().unwrap();
+
+        // act
+        let _ = handle.get(&provider2);
+
+        // assert
+        // panics
+    }
+}
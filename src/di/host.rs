@@ -0,0 +1,262 @@
+use crate::{ServiceCollection, ServiceProvider, ServiceRef, ValidationError};
+use std::sync::{Arc, Condvar, Mutex};
+
+#[cfg(feature = "signal")]
+use std::io;
+
+/// Defines the behavior of a service that is started and stopped by a [host](struct.Host.html).
+pub trait HostedService {
+    /// Starts the service.
+    fn start(&self);
+
+    /// Stops the service.
+    fn stop(&self);
+}
+
+/// Represents a handle used to request that a [host](struct.Host.html) shut down.
+///
+/// # Remarks
+///
+/// A shutdown handle can be sent to another thread, such as a signal handler.
+#[derive(Clone, Default)]
+pub struct ShutdownHandle {
+    state: Arc<(Mutex<bool>, Condvar)>,
+}
+
+impl ShutdownHandle {
+    /// Requests that the associated host shut down.
+    pub fn shutdown(&self) {
+        let (requested, signal) = &*self.state;
+        *requested.lock().unwrap() = true;
+        signal.notify_all();
+    }
+
+    /// Returns true if shutdown has been requested.
+    pub fn is_shutdown_requested(&self) -> bool {
+        *self.state.0.lock().unwrap()
+    }
+
+    /// Blocks the current thread until shutdown is requested.
+    pub fn wait(&self) {
+        let (requested, signal) = &*self.state;
+        let mut requested = requested.lock().unwrap();
+
+        while !*requested {
+            requested = signal.wait(requested).unwrap();
+        }
+    }
+}
+
+/// Represents an application host that manages the lifecycle of [hosted services](trait.HostedService.html).
+///
+/// # Remarks
+///
+/// Hosted services are started in the order they were registered and stopped in the reverse
+/// order. When the host is dropped, any started services are stopped before the root
+/// [service provider](struct.ServiceProvider.html) is disposed.
+pub struct Host {
+    running: Vec<ServiceRef<dyn HostedService>>,
+    provider: ServiceProvider,
+    shutdown: ShutdownHandle,
+}
+
+impl Host {
+    /// Builds and returns a new host.
+    ///
+    /// # Arguments
+    ///
+    /// * `services` - The [service collection](struct.ServiceCollection.html) used to build the root service provider
+    pub fn build(services: &ServiceCollection) -> Result<Self, ValidationError> {
+        Ok(Self {
+            running: Vec::new(),
+            provider: services.build_provider()?,
+            shutdown: ShutdownHandle::default(),
+        })
+    }
+
+    /// Gets the root [service provider](struct.ServiceProvider.html) of the host.
+    pub fn services(&self) -> &ServiceProvider {
+        &self.provider
+    }
+
+    /// Gets a [handle](struct.ShutdownHandle.html) that can be used to request that the host shut down.
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        self.shutdown.clone()
+    }
+
+    /// Requests that the host shut down when the process receives a termination signal.
+    ///
+    /// # Remarks
+    ///
+    /// This function is only available with the **signal** feature. The signals are Ctrl-C on
+    /// all platforms as well as `SIGINT`, `SIGTERM`, and `SIGHUP` on Unix. A process can only
+    /// have one such handler; registering another one results in an error.
+    #[cfg(feature = "signal")]
+    pub fn shutdown_on_signal(&self) -> io::Result<()> {
+        let shutdown = self.shutdown.clone();
+
+        ctrlc::set_handler(move || shutdown.shutdown()).map_err(|error| match error {
+            ctrlc::Error::System(error) => error,
+            error => io::Error::other(error),
+        })
+    }
+
+    /// Starts all hosted services in the order they were registered.
+    ///
+    /// # Remarks
+    ///
+    /// Starting a host that has already been started has no effect.
+    pub fn start(&mut self) {
+        if !self.running.is_empty() {
+            return;
+        }
+
+        for service in self.provider.get_all::<dyn HostedService>() {
+            service.start();
+            self.running.push(service);
+        }
+    }
+
+    /// Stops all started hosted services in the reverse order they were started.
+    pub fn stop(&mut self) {
+        while let Some(service) = self.running.pop() {
+            service.stop();
+        }
+    }
+
+    /// Starts the host, blocks the current thread until shutdown is requested,
+    /// and then stops the host and disposes the root service provider.
+    pub fn run(mut self) {
+        self.start();
+        self.shutdown.wait();
+    }
+}
+
+impl Drop for Host {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{hosting::*, *};
+    use std::sync::Mutex;
+    use std::thread;
+
+    struct RecordingService {
+        name: &'static str,
+        events: ServiceRef<Mutex<Vec<String>>>,
+    }
+
+    impl RecordingService {
+        fn new(name: &'static str, events: ServiceRef<Mutex<Vec<String>>>) -> Self {
+            Self { name, events }
+        }
+    }
+
+    impl HostedService for RecordingService {
+        fn start(&self) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("start {}", self.name));
+        }
+
+        fn stop(&self) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("stop {}", self.name));
+        }
+    }
+
+    #[test]
+    fn start_should_start_services_in_registration_order() {
+        // arrange
+        let events = ServiceRef::new(Mutex::new(Vec::new()));
+        let (first, second) = (events.clone(), events.clone());
+        let mut services = ServiceCollection::new();
+
+        services
+            .add(
+                singleton::<dyn HostedService, RecordingService>()
+                    .from(move |_| ServiceRef::new(RecordingService::new("first", first.clone()))),
+            )
+            .add(
+                singleton::<dyn HostedService, RecordingService>().from(move |_| {
+                    ServiceRef::new(RecordingService::new("second", second.clone()))
+                }),
+            );
+
+        let mut host = Host::build(&services).unwrap();
+
+        // act
+        host.start();
+
+        // assert
+        assert_eq!(&*events.lock().unwrap(), &["start first", "start second"]);
+    }
+
+    #[test]
+    fn drop_should_stop_services_in_reverse_order() {
+        // arrange
+        let events = ServiceRef::new(Mutex::new(Vec::new()));
+        let (first, second) = (events.clone(), events.clone());
+        let mut services = ServiceCollection::new();
+
+        services
+            .add(
+                singleton::<dyn HostedService, RecordingService>()
+                    .from(move |_| ServiceRef::new(RecordingService::new("first", first.clone()))),
+            )
+            .add(
+                singleton::<dyn HostedService, RecordingService>().from(move |_| {
+                    ServiceRef::new(RecordingService::new("second", second.clone()))
+                }),
+            );
+
+        let mut host = Host::build(&services).unwrap();
+
+        host.start();
+
+        // act
+        drop(host);
+
+        // assert
+        assert_eq!(
+            &*events.lock().unwrap(),
+            &["start first", "start second", "stop second", "stop first"]
+        );
+    }
+
+    #[test]
+    fn run_should_stop_services_when_shutdown_is_requested() {
+        // arrange
+        let events = ServiceRef::new(Mutex::new(Vec::new()));
+        let (first, second) = (events.clone(), events.clone());
+        let mut services = ServiceCollection::new();
+
+        services
+            .add(
+                singleton::<dyn HostedService, RecordingService>()
+                    .from(move |_| ServiceRef::new(RecordingService::new("first", first.clone()))),
+            )
+            .add(
+                singleton::<dyn HostedService, RecordingService>().from(move |_| {
+                    ServiceRef::new(RecordingService::new("second", second.clone()))
+                }),
+            );
+
+        let host = Host::build(&services).unwrap();
+        let shutdown = host.shutdown_handle();
+        let requester = thread::spawn(move || shutdown.shutdown());
+
+        // act
+        host.run();
+
+        // assert
+        requester.join().unwrap();
+        assert_eq!(events.lock().unwrap().len(), 4);
+    }
+}
@@ -10,19 +10,63 @@ pub trait Injectable: Sized {
     fn inject(lifetime: ServiceLifetime) -> ServiceDescriptor;
 
     /// Creates and returns a [service descriptor](struct.ServiceDescriptor.html) for a singleton injected type.
+    #[track_caller]
     fn singleton() -> ServiceDescriptor {
         Self::inject(ServiceLifetime::Singleton)
     }
 
     /// Creates and returns a [service descriptor](struct.ServiceDescriptor.html) for a scoped injected type.
+    #[track_caller]
     fn scoped() -> ServiceDescriptor {
         Self::inject(ServiceLifetime::Scoped)
     }
 
     /// Creates and returns a [service descriptor](struct.ServiceDescriptor.html) for a transient injected injected.
+    #[track_caller]
     fn transient() -> ServiceDescriptor {
         Self::inject(ServiceLifetime::Transient)
     }
+
+    /// Creates and returns a [service descriptor](struct.ServiceDescriptor.html) for a tenant injected type.
+    #[track_caller]
+    fn tenant() -> ServiceDescriptor {
+        Self::inject(ServiceLifetime::Tenant)
+    }
+
+    /// Creates and returns a [service descriptor](struct.ServiceDescriptor.html) for a per-thread injected type.
+    #[track_caller]
+    fn per_thread() -> ServiceDescriptor {
+        Self::inject(ServiceLifetime::PerThread)
+    }
+}
+
+/// Defines the behavior of an injectable type that is created from runtime arguments.
+///
+/// # Remarks
+///
+/// The injectable type is registered as a [ParamFactory](struct.ParamFactory.html), which resolves
+/// services from the [service provider](struct.ServiceProvider.html) that resolved the factory itself.
+/// A factory that outlives that provider would create services from a disposed or foreign scope;
+/// therefore, it can only be registered with a transient or scoped lifetime.
+pub trait ParamInjectable: Sized {
+    /// Creates and returns a [service descriptor](struct.ServiceDescriptor.html) for the factory of an injectable type.
+    ///
+    /// # Arguments
+    ///
+    /// * `lifetime` - The [lifetime](enum.ServiceLifetime.html) of the factory, which must be transient or scoped
+    fn inject(lifetime: ServiceLifetime) -> ServiceDescriptor;
+
+    /// Creates and returns a [service descriptor](struct.ServiceDescriptor.html) for a transient factory.
+    #[track_caller]
+    fn transient() -> ServiceDescriptor {
+        Self::inject(ServiceLifetime::Transient)
+    }
+
+    /// Creates and returns a [service descriptor](struct.ServiceDescriptor.html) for a scoped factory.
+    #[track_caller]
+    fn scoped() -> ServiceDescriptor {
+        Self::inject(ServiceLifetime::Scoped)
+    }
 }
 
 #[cfg(test)]
@@ -30,7 +74,7 @@ mod tests {
     use super::*;
     use crate::*;
 
-    trait TestService {}
+    trait TestService: Shareable {}
     trait OtherTestService {}
 
     #[derive(Default)]
@@ -0,0 +1,59 @@
+use crate::{ServiceDescriptor, ServiceProvider, ServiceRef, Shareable};
+use std::any::Any;
+
+/// Represents the possible outcomes of intercepting a service resolution.
+pub enum Interception {
+    /// Indicates that the service should be resolved from its descriptor.
+    Proceed,
+
+    /// Indicates that the service should be replaced with the specified instance.
+    ///
+    /// # Remarks
+    ///
+    /// The instance must have the same shape as an instance returned by
+    /// [ServiceDescriptor::get](struct.ServiceDescriptor.html#method.get), which is a
+    /// `ServiceRef<dyn Any>` that contains a `ServiceRef<T>` of the service type.
+    Replace(ServiceRef<dyn Any>),
+
+    /// Indicates that the service must not be resolved.
+    Deny,
+}
+
+/// Defines the behavior of an interceptor that participates in service resolution.
+///
+/// # Remarks
+///
+/// Every method has a default implementation that does not change service resolution.
+/// Interceptors are invoked in the order they were added. When the **async** feature
+/// is enabled, an interceptor must be `Send` and `Sync`.
+pub trait Interceptor: Shareable {
+    /// Intercepts a service before it is resolved.
+    ///
+    /// # Arguments
+    ///
+    /// * `descriptor` - The [service descriptor](struct.ServiceDescriptor.html) being resolved
+    /// * `services` - The [service provider](struct.ServiceProvider.html) resolving the service
+    fn intercept(
+        &self,
+        _descriptor: &ServiceDescriptor,
+        _services: &ServiceProvider,
+    ) -> Interception {
+        Interception::Proceed
+    }
+
+    /// Processes a service instance after it has been created and before it is cached, if ever.
+    ///
+    /// # Arguments
+    ///
+    /// * `descriptor` - The [service descriptor](struct.ServiceDescriptor.html) that created the instance
+    /// * `services` - The [service provider](struct.ServiceProvider.html) that created the instance
+    /// * `instance` - The created instance
+    fn created(
+        &self,
+        _descriptor: &ServiceDescriptor,
+        _services: &ServiceProvider,
+        instance: ServiceRef<dyn Any>,
+    ) -> ServiceRef<dyn Any> {
+        instance
+    }
+}
@@ -24,6 +24,35 @@ impl<T> Lazy<T> {
     }
 }
 
+/// Represents a factory that resolves a new service each time it is invoked.
+///
+/// # Remarks
+///
+/// The underlying service is resolved according to its registered
+/// [lifetime](enum.ServiceLifetime.html). A new instance is created for every
+/// invocation of a transient service.
+pub struct Factory<T> {
+    services: ServiceProvider,
+    resolve: fn(&ServiceProvider) -> T,
+}
+
+impl<T> Factory<T> {
+    fn new(services: ServiceProvider, resolve: fn(&ServiceProvider) -> T) -> Self {
+        Self { services, resolve }
+    }
+
+    /// Resolves and returns the underlying service.
+    pub fn create(&self) -> T {
+        (self.resolve)(&self.services)
+    }
+}
+
+impl<T> Clone for Factory<T> {
+    fn clone(&self) -> Self {
+        Self::new(self.services.clone(), self.resolve)
+    }
+}
+
 fn to_vec<T: Any + ?Sized>(services: &ServiceProvider) -> Vec<ServiceRef<T>> {
     services.get_all::<T>().collect()
 }
@@ -60,6 +89,40 @@ pub fn zero_or_more<T: Any + ?Sized>(services: ServiceProvider) -> Lazy<Vec<Serv
     Lazy::new(services, to_vec::<T>)
 }
 
+/// Creates and returns a factory for a required service.
+///
+/// # Arguments
+///
+/// * `services` - The [service provider](struct.ServiceProvider.html) used to resolve the service
+#[inline]
+pub fn exactly_one_factory<T: Any + ?Sized>(services: ServiceProvider) -> Factory<ServiceRef<T>> {
+    Factory::new(services, ServiceProvider::get_required::<T>)
+}
+
+/// Creates and returns a factory for an optional service.
+///
+/// # Arguments
+///
+/// * `services` - The [service provider](struct.ServiceProvider.html) used to resolve the service
+#[inline]
+pub fn zero_or_one_factory<T: Any + ?Sized>(
+    services: ServiceProvider,
+) -> Factory<Option<ServiceRef<T>>> {
+    Factory::new(services, ServiceProvider::get::<T>)
+}
+
+/// Creates and returns a factory for multiple services.
+///
+/// # Arguments
+///
+/// * `services` - The [service provider](struct.ServiceProvider.html) used to resolve the services
+#[inline]
+pub fn zero_or_more_factory<T: Any + ?Sized>(
+    services: ServiceProvider,
+) -> Factory<Vec<ServiceRef<T>>> {
+    Factory::new(services, to_vec::<T>)
+}
+
 /// Creates and return a holder for a lazy-initialized, optional service that is missing.
 #[inline]
 pub fn missing<T: Any + ?Sized>() -> Lazy<Option<ServiceRef<T>>> {
@@ -73,6 +136,7 @@ pub fn empty<T: Any + ?Sized>() -> Lazy<Vec<ServiceRef<T>>> {
 }
 
 #[cfg(test)]
+#[allow(clippy::default_constructed_unit_structs)]
 mod tests {
 
     use crate::lazy::{self, Lazy};
@@ -177,6 +241,41 @@ mod tests {
         assert_eq!(None, foo.echo());
     }
 
+    #[test]
+    #[allow(ambiguous_wide_pointer_comparisons)]
+    fn factory_should_create_new_transient_service() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add(transient_as_self::<Bar>().from(|_| ServiceRef::new(Bar)))
+            .build_provider()
+            .unwrap();
+        let factory = lazy::exactly_one_factory::<Bar>(provider);
+
+        // act
+        let bar1 = factory.create();
+        let bar2 = factory.create();
+
+        // assert
+        assert!(!ServiceRef::ptr_eq(&bar1, &bar2));
+    }
+
+    #[test]
+    fn factory_should_create_all_services() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add(transient_as_self::<Bar>().from(|_| ServiceRef::new(Bar)))
+            .add(transient_as_self::<Bar>().from(|_| ServiceRef::new(Bar)))
+            .build_provider()
+            .unwrap();
+        let factory = lazy::zero_or_more_factory::<Bar>(provider);
+
+        // act
+        let bars = factory.create();
+
+        // assert
+        assert_eq!(bars.len(), 2);
+    }
+
     #[test]
     fn missing_should_initialize_lazy() {
         // arrange
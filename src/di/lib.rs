@@ -1,9 +1,21 @@
 #![doc = include_str!("README.md")]
+#![allow(clippy::needless_doctest_main)]
 
+mod binding;
 mod collection;
+mod condition;
 mod dependency;
 mod descriptor;
+mod handle;
+mod interceptor;
+mod lifetime;
+mod manifest;
+mod observer;
+mod ordering;
+mod param_factory;
 mod provider;
+mod tenant;
+mod thread;
 mod r#type;
 mod validation;
 
@@ -16,13 +28,31 @@ mod inject;
 #[cfg(feature = "lazy")]
 mod lazy_init;
 
+#[cfg(feature = "testing")]
+mod test_provider;
+
+#[cfg(feature = "options")]
+mod options_config;
+
+#[cfg(feature = "hosting")]
+mod host;
+
 #[cfg(test)]
 mod test;
 
+pub use binding::*;
 pub use collection::*;
+pub use condition::*;
 pub use dependency::*;
 pub use descriptor::*;
+pub use handle::*;
+pub use interceptor::*;
+pub use lifetime::*;
+pub use manifest::*;
+pub use observer::*;
+pub use param_factory::*;
 pub use provider::*;
+pub use tenant::*;
 pub use r#type::*;
 pub use validation::*;
 
@@ -39,5 +69,32 @@ pub use di_macros::{inject, injectable};
 #[cfg(feature = "lazy")]
 pub mod lazy {
     use super::*;
-    pub use lazy_init::{empty, exactly_one, missing, zero_or_more, zero_or_one, Lazy};
+    pub use lazy_init::{
+        empty, exactly_one, exactly_one_factory, missing, zero_or_more, zero_or_more_factory,
+        zero_or_one, zero_or_one_factory, Factory, Lazy,
+    };
+}
+
+/// Contains support for the options pattern.
+#[cfg(feature = "options")]
+pub mod options {
+    use super::*;
+    pub use options_config::{
+        ConfigureOptions, Options, OptionsSnapshot, OptionsValidator, PostConfigureOptions,
+        ValidateOptions,
+    };
+}
+
+/// Contains support for testing with a service provider.
+#[cfg(feature = "testing")]
+pub mod testing {
+    use super::*;
+    pub use test_provider::{TestServiceProvider, TestServiceProviderBuilder};
+}
+
+/// Contains support for hosting services.
+#[cfg(feature = "hosting")]
+pub mod hosting {
+    use super::*;
+    pub use host::{Host, HostedService, ShutdownHandle};
 }
@@ -0,0 +1,214 @@
+use crate::{ServiceDescriptor, ServiceProvider, ServiceRef, Shareable};
+use std::any::Any;
+
+/// Defines the behavior of a custom [service lifetime](enum.ServiceLifetime.html).
+///
+/// # Remarks
+///
+/// A lifetime strategy owns the caching of the instances of the services registered with it,
+/// which allows lifetimes such as per-thread, pooled, or time-expiring instances. The
+/// [width](#tymethod.width) of a lifetime describes how long its instances live relative to
+/// other lifetimes and is used to detect captive dependencies when a service provider is built.
+/// When the **async** feature is enabled, a lifetime strategy must be `Send` and `Sync`.
+/// Two custom lifetimes are equal when they refer to the same strategy instance.
+pub trait LifetimeStrategy: Any + Shareable {
+    /// Gets the name of the lifetime.
+    fn name(&self) -> &str;
+
+    /// Gets the width of the lifetime relative to other lifetimes.
+    ///
+    /// # Remarks
+    ///
+    /// A service must not depend on a service whose lifetime has a smaller, non-zero width.
+    /// The built-in lifetimes have the following widths:
+    ///
+    /// * [transient](enum.ServiceLifetime.html#variant.Transient) - 0
+    /// * [scoped](enum.ServiceLifetime.html#variant.Scoped) - 100
+    /// * [tenant](enum.ServiceLifetime.html#variant.Tenant) - 200
+    /// * [singleton](enum.ServiceLifetime.html#variant.Singleton) - 300
+    ///
+    /// A [per-thread](enum.ServiceLifetime.html#variant.PerThread) lifetime is not ordered by width.
+    /// A custom lifetime with a non-zero width must not depend on a per-thread service and a
+    /// per-thread service must not depend on a custom lifetime with a width smaller than a singleton.
+    fn width(&self) -> usize;
+
+    /// Gets or creates an instance of the service defined by the specified descriptor.
+    ///
+    /// # Arguments
+    ///
+    /// * `descriptor` - The [descriptor](struct.ServiceDescriptor.html) of the resolved service
+    /// * `services` - The current [service provider](struct.ServiceProvider.html)
+    /// * `create` - The function used to create a new instance of the service
+    ///
+    /// # Remarks
+    ///
+    /// The [cache key](struct.ServiceDescriptor.html#method.cache_key) of the descriptor can be
+    /// used to identify the cached instances of the service.
+    fn get(
+        &self,
+        descriptor: &ServiceDescriptor,
+        services: &ServiceProvider,
+        create: &dyn Fn() -> ServiceRef<dyn Any>,
+    ) -> ServiceRef<dyn Any>;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{test::*, *};
+    use std::any::Any;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    // counts the instances created for each descriptor and never caches them
+    #[derive(Default)]
+    struct Counted {
+        counts: Mutex<HashMap<usize, usize>>,
+    }
+
+    impl LifetimeStrategy for Counted {
+        fn name(&self) -> &str {
+            "counted"
+        }
+
+        fn width(&self) -> usize {
+            150
+        }
+
+        fn get(
+            &self,
+            descriptor: &ServiceDescriptor,
+            _services: &ServiceProvider,
+            create: &dyn Fn() -> ServiceRef<dyn Any>,
+        ) -> ServiceRef<dyn Any> {
+            *self
+                .counts
+                .lock()
+                .unwrap()
+                .entry(descriptor.cache_key())
+                .or_default() += 1;
+            create()
+        }
+    }
+
+    #[test]
+    fn get_should_resolve_service_with_custom_lifetime() {
+        // arrange
+        let strategy: &'static Counted = Box::leak(Box::default());
+        let provider = ServiceCollection::new()
+            .add(custom_factory(strategy, |_| {
+                ServiceRef::new(TestServiceImpl::default()) as ServiceRef<dyn TestService>
+            }))
+            .build_provider()
+            .unwrap();
+
+        // act
+        let _ = provider.get_required::<dyn TestService>();
+        let _ = provider.get_required::<dyn TestService>();
+
+        // assert
+        let descriptor = provider.descriptor::<dyn TestService>().unwrap();
+        let counts = strategy.counts.lock().unwrap();
+        assert_eq!(counts[&descriptor.cache_key()], 2);
+        assert_eq!(descriptor.lifetime(), ServiceLifetime::Custom(strategy));
+    }
+
+    #[test]
+    fn cache_key_should_not_be_reused_by_another_provider() {
+        // arrange
+        let strategy: &'static Counted = Box::leak(Box::default());
+        let mut services = ServiceCollection::new();
+
+        services.add(custom_factory(strategy, |_| {
+            ServiceRef::new(TestServiceImpl::default()) as ServiceRef<dyn TestService>
+        }));
+
+        let provider = services.build_provider().unwrap();
+        let key = provider
+            .descriptor::<dyn TestService>()
+            .unwrap()
+            .cache_key();
+
+        drop(provider);
+
+        // act
+        let provider = services.build_provider().unwrap();
+
+        // assert
+        let descriptor = provider.descriptor::<dyn TestService>().unwrap();
+        assert_ne!(descriptor.cache_key(), key);
+    }
+
+    struct First;
+    struct Second;
+
+    impl LifetimeStrategy for First {
+        fn name(&self) -> &str {
+            "first"
+        }
+
+        fn width(&self) -> usize {
+            0
+        }
+
+        fn get(
+            &self,
+            _descriptor: &ServiceDescriptor,
+            _services: &ServiceProvider,
+            create: &dyn Fn() -> ServiceRef<dyn Any>,
+        ) -> ServiceRef<dyn Any> {
+            create()
+        }
+    }
+
+    impl LifetimeStrategy for Second {
+        fn name(&self) -> &str {
+            "second"
+        }
+
+        fn width(&self) -> usize {
+            0
+        }
+
+        fn get(
+            &self,
+            _descriptor: &ServiceDescriptor,
+            _services: &ServiceProvider,
+            create: &dyn Fn() -> ServiceRef<dyn Any>,
+        ) -> ServiceRef<dyn Any> {
+            create()
+        }
+    }
+
+    #[test]
+    fn lifetime_should_not_compare_zero_sized_strategies_by_address_only() {
+        // arrange
+        let first: &'static dyn LifetimeStrategy = Box::leak(Box::new(First));
+        let second: &'static dyn LifetimeStrategy = Box::leak(Box::new(Second));
+
+        // act
+        let equal = ServiceLifetime::Custom(first) == ServiceLifetime::Custom(second);
+
+        // assert
+        assert!(!equal);
+        assert_eq!(
+            ServiceLifetime::Custom(first),
+            ServiceLifetime::Custom(first)
+        );
+    }
+
+    #[test]
+    fn lifetime_should_compare_custom_strategies_by_identity() {
+        // arrange
+        let first = ServiceLifetime::Custom(Box::leak(Box::<Counted>::default()));
+        let second = ServiceLifetime::Custom(Box::leak(Box::<Counted>::default()));
+
+        // act
+        let equal = first == second;
+
+        // assert
+        assert!(!equal);
+        assert_eq!(first, first);
+        assert_eq!(first.name(), "counted");
+        assert_eq!(first.width(), 150);
+    }
+}
@@ -0,0 +1,374 @@
+use crate::{ServiceCollection, ServiceDependency};
+use std::collections::HashMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Represents a dependency recorded in a [service manifest](struct.Manifest.html).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ManifestDependency {
+    /// Gets the name of the injected type.
+    pub injected_type: String,
+
+    /// Gets the cardinality of the dependency.
+    pub cardinality: String,
+
+    /// Gets a value indicating whether the dependency is resolved lazily.
+    pub lazy: bool,
+}
+
+impl From<&ServiceDependency> for ManifestDependency {
+    fn from(dependency: &ServiceDependency) -> Self {
+        Self {
+            injected_type: dependency.injected_type().name().to_owned(),
+            cardinality: format!("{:?}", dependency.cardinality()),
+            lazy: dependency.is_lazy(),
+        }
+    }
+}
+
+/// Represents a registration recorded in a [service manifest](struct.Manifest.html).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ManifestEntry {
+    /// Gets the name of the service type.
+    pub service_type: String,
+
+    /// Gets the name of the implementation type.
+    pub implementation_type: String,
+
+    /// Gets the lifetime of the service.
+    pub lifetime: String,
+
+    /// Gets the dependencies of the service.
+    pub dependencies: Vec<ManifestDependency>,
+
+    /// Gets the source code location where the service was registered.
+    pub origin: String,
+}
+
+impl ManifestEntry {
+    fn key(&self) -> (&str, &str) {
+        (&self.service_type, &self.implementation_type)
+    }
+
+    // the origin is expected to change between builds and is not a change in registration
+    fn is_changed(&self, other: &Self) -> bool {
+        self.lifetime != other.lifetime || self.dependencies != other.dependencies
+    }
+}
+
+/// Represents a frozen snapshot of the services registered in a [service collection](struct.ServiceCollection.html).
+///
+/// # Remarks
+///
+/// Entries are sorted by service type and then implementation type. Registrations with the
+/// same service and implementation type retain the order in which they were registered.
+/// Conditional registrations are only included when their conditions are met.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Manifest {
+    entries: Vec<ManifestEntry>,
+}
+
+impl Manifest {
+    /// Initializes a new service manifest.
+    ///
+    /// # Arguments
+    ///
+    /// * `entries` - The [entries](struct.ManifestEntry.html) in the manifest
+    pub fn new(mut entries: Vec<ManifestEntry>) -> Self {
+        entries.sort_by(|a, b| a.key().cmp(&b.key()));
+        Self { entries }
+    }
+
+    /// Gets the entries in the manifest.
+    pub fn entries(&self) -> &[ManifestEntry] {
+        &self.entries
+    }
+
+    // pairs each entry with the number of preceding entries that have the same key
+    fn keyed(&self) -> HashMap<(&str, &str, usize), &ManifestEntry> {
+        let mut occurrences = HashMap::new();
+        let mut keyed = HashMap::with_capacity(self.entries.len());
+
+        for entry in &self.entries {
+            let count = occurrences.entry(entry.key()).or_insert(0usize);
+            keyed.insert((entry.key().0, entry.key().1, *count), entry);
+            *count += 1;
+        }
+
+        keyed
+    }
+}
+
+impl From<&ServiceCollection> for Manifest {
+    fn from(services: &ServiceCollection) -> Self {
+        // conditional registrations are evaluated exactly as they are when a provider is built
+        let (accepted, _) = services.evaluate_conditions(&[]);
+
+        Self::new(
+            services
+                .iter()
+                .chain(accepted)
+                .map(|descriptor| ManifestEntry {
+                    service_type: descriptor.service_type().name().to_owned(),
+                    implementation_type: descriptor.implementation_type().name().to_owned(),
+                    lifetime: format!("{:?}", descriptor.lifetime()),
+                    dependencies: descriptor.dependencies().iter().map(Into::into).collect(),
+                    origin: descriptor.origin().to_string(),
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Represents the differences between two [service manifests](struct.Manifest.html).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ManifestDiff {
+    /// Gets the registrations that were added.
+    pub added: Vec<ManifestEntry>,
+
+    /// Gets the registrations that were removed.
+    pub removed: Vec<ManifestEntry>,
+
+    /// Gets the registrations that were changed as pairs of the original and current registration.
+    pub changed: Vec<(ManifestEntry, ManifestEntry)>,
+}
+
+impl ManifestDiff {
+    /// Returns true if there are no differences.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Compares two service manifests and returns their differences.
+///
+/// # Arguments
+///
+/// * `original` - The original [service manifest](struct.Manifest.html)
+/// * `current` - The current [service manifest](struct.Manifest.html)
+///
+/// # Remarks
+///
+/// A registration is identified by its service type and implementation type. A registration
+/// is changed when its lifetime or dependencies differ. The origin of a registration is not compared.
+pub fn diff(original: &Manifest, current: &Manifest) -> ManifestDiff {
+    let before = original.keyed();
+    let after = current.keyed();
+    let mut result = ManifestDiff::default();
+
+    for (key, entry) in &before {
+        match after.get(key) {
+            Some(other) if entry.is_changed(other) => {
+                result.changed.push(((*entry).clone(), (*other).clone()))
+            }
+            Some(_) => {}
+            None => result.removed.push((*entry).clone()),
+        }
+    }
+
+    for (key, entry) in &after {
+        if !before.contains_key(key) {
+            result.added.push((*entry).clone());
+        }
+    }
+
+    result.added.sort_by(|a, b| a.key().cmp(&b.key()));
+    result.removed.sort_by(|a, b| a.key().cmp(&b.key()));
+    result.changed.sort_by(|a, b| a.0.key().cmp(&b.0.key()));
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test::*, *};
+
+    #[test]
+    fn manifest_should_contain_sorted_registrations() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services
+            .add(
+                transient::<dyn OtherTestService, OtherTestServiceImpl>()
+                    .depends_on(exactly_one::<dyn TestService>())
+                    .from(|sp| {
+                        ServiceRef::new(OtherTestServiceImpl::new(
+                            sp.get_required::<dyn TestService>(),
+                        ))
+                    }),
+            )
+            .add(
+                singleton::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl::default())),
+            );
+
+        // act
+        let manifest = services.manifest();
+
+        // assert
+        let entries = manifest.entries();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].service_type, "dyn di::test::OtherTestService");
+        assert_eq!(entries[0].lifetime, "Transient");
+        assert_eq!(
+            entries[0].dependencies,
+            [ManifestDependency {
+                injected_type: "dyn di::test::TestService".into(),
+                cardinality: "ExactlyOne".into(),
+                lazy: false,
+            }]
+        );
+        assert_eq!(entries[1].implementation_type, "di::test::TestServiceImpl");
+        assert!(entries[1].origin.starts_with(file!()));
+    }
+
+    #[test]
+    fn manifest_should_contain_conditional_registrations_whose_conditions_are_met() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services
+            .add_when(
+                || true,
+                singleton::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl::default())),
+            )
+            .add_when(
+                || false,
+                singleton::<dyn TestService, TestService2Impl>()
+                    .from(|_| ServiceRef::new(TestService2Impl::default())),
+            );
+
+        // act
+        let manifest = services.manifest();
+
+        // assert
+        let entries = manifest.entries();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].implementation_type, "di::test::TestServiceImpl");
+    }
+
+    #[test]
+    fn diff_should_report_added_removed_and_changed_registrations() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services
+            .add(
+                transient::<dyn OtherTestService, OtherTestServiceImpl>()
+                    .depends_on(exactly_one::<dyn TestService>())
+                    .from(|sp| {
+                        ServiceRef::new(OtherTestServiceImpl::new(
+                            sp.get_required::<dyn TestService>(),
+                        ))
+                    }),
+            )
+            .add(
+                singleton::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl::default())),
+            );
+
+        let original = services.manifest();
+
+        services.remove(0);
+        services
+            .add(
+                transient::<dyn OtherTestService, OtherTestServiceImpl>().from(|sp| {
+                    ServiceRef::new(OtherTestServiceImpl::new(
+                        sp.get_required::<dyn TestService>(),
+                    ))
+                }),
+            )
+            .add(
+                scoped::<dyn AnotherTestService, AnotherTestServiceImpl>().from(|sp| {
+                    ServiceRef::new(AnotherTestServiceImpl::new(
+                        sp.get_required::<dyn OtherTestService>(),
+                    ))
+                }),
+            );
+        services.remove(0);
+
+        let current = services.manifest();
+
+        // act
+        let result = diff(&original, &current);
+
+        // assert
+        assert_eq!(result.added.len(), 1);
+        assert_eq!(
+            result.added[0].service_type,
+            "dyn di::test::AnotherTestService"
+        );
+        assert_eq!(result.removed.len(), 1);
+        assert_eq!(result.removed[0].service_type, "dyn di::test::TestService");
+        assert_eq!(result.changed.len(), 1);
+        assert!(result.changed[0].1.dependencies.is_empty());
+    }
+
+    #[test]
+    fn diff_should_be_empty_for_same_registrations() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services
+            .add(
+                transient::<dyn OtherTestService, OtherTestServiceImpl>()
+                    .depends_on(exactly_one::<dyn TestService>())
+                    .from(|sp| {
+                        ServiceRef::new(OtherTestServiceImpl::new(
+                            sp.get_required::<dyn TestService>(),
+                        ))
+                    }),
+            )
+            .add(
+                singleton::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl::default())),
+            );
+
+        let original = services.manifest();
+        let current = services.manifest();
+
+        // act
+        let result = diff(&original, &current);
+
+        // assert
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn manifest_should_round_trip_through_json() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services
+            .add(
+                transient::<dyn OtherTestService, OtherTestServiceImpl>()
+                    .depends_on(exactly_one::<dyn TestService>())
+                    .from(|sp| {
+                        ServiceRef::new(OtherTestServiceImpl::new(
+                            sp.get_required::<dyn TestService>(),
+                        ))
+                    }),
+            )
+            .add(
+                singleton::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl::default())),
+            );
+
+        let manifest = services.manifest();
+
+        // act
+        let json = serde_json::to_string(&manifest).unwrap();
+        let result: Manifest = serde_json::from_str(&json).unwrap();
+
+        // assert
+        assert_eq!(result, manifest);
+    }
+}
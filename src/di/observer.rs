@@ -0,0 +1,69 @@
+use crate::{ServiceDescriptor, Shareable, Type};
+use std::time::Duration;
+
+/// Defines the behavior of an observer that receives service resolution events.
+///
+/// # Remarks
+///
+/// Every method has a default implementation that does nothing, which allows an observer
+/// to only implement the events it is interested in. A scope is identified by the value
+/// returned from [ServiceProvider::scope_id](struct.ServiceProvider.html#method.scope_id).
+/// When the **async** feature is enabled, an observer must be `Send` and `Sync`.
+pub trait ResolutionObserver: Shareable {
+    /// Occurs when a service is about to be resolved.
+    ///
+    /// # Arguments
+    ///
+    /// * `descriptor` - The [service descriptor](struct.ServiceDescriptor.html) being resolved
+    /// * `scope` - The identifier of the scope resolving the service
+    fn on_resolving(&self, _descriptor: &ServiceDescriptor, _scope: usize) {}
+
+    /// Occurs when a service has been resolved.
+    ///
+    /// # Arguments
+    ///
+    /// * `descriptor` - The resolved [service descriptor](struct.ServiceDescriptor.html)
+    /// * `scope` - The identifier of the scope that resolved the service
+    fn on_resolved(&self, _descriptor: &ServiceDescriptor, _scope: usize) {}
+
+    /// Occurs when a service is resolved from a previously created instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `descriptor` - The resolved [service descriptor](struct.ServiceDescriptor.html)
+    /// * `scope` - The identifier of the scope that resolved the service
+    fn on_cache_hit(&self, _descriptor: &ServiceDescriptor, _scope: usize) {}
+
+    /// Occurs when a new service instance has been created.
+    ///
+    /// # Arguments
+    ///
+    /// * `descriptor` - The [service descriptor](struct.ServiceDescriptor.html) whose factory created the instance
+    /// * `scope` - The identifier of the scope that created the instance
+    /// * `elapsed` - The amount of time spent in the factory
+    fn on_created(&self, _descriptor: &ServiceDescriptor, _scope: usize, _elapsed: Duration) {}
+
+    /// Occurs when a new scope has been created.
+    ///
+    /// # Arguments
+    ///
+    /// * `scope` - The identifier of the new scope
+    /// * `parent` - The identifier of the scope the new scope was created from
+    fn on_scope_created(&self, _scope: usize, _parent: usize) {}
+
+    /// Occurs when a scope has been dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `scope` - The identifier of the dropped scope
+    fn on_scope_dropped(&self, _scope: usize) {}
+
+    /// Occurs when a service cannot be resolved because it has not been registered
+    /// or was denied by an [interceptor](trait.Interceptor.html).
+    ///
+    /// # Arguments
+    ///
+    /// * `service_type` - The [type](struct.Type.html) of service that could not be resolved
+    /// * `scope` - The identifier of the scope that attempted to resolve the service
+    fn on_failed(&self, _service_type: &Type, _scope: usize) {}
+}
@@ -0,0 +1,380 @@
+use crate::{
+    existing_as_self, singleton_as_self, zero_or_more, ServiceCollection, ServiceDescriptorBuilder,
+    ServiceLifetime, ServiceProvider, ServiceRef, Type,
+};
+use std::any::type_name;
+use std::ops::Deref;
+
+/// Represents configured options that are created once.
+pub struct Options<T> {
+    value: T,
+}
+
+impl<T> Options<T> {
+    /// Initializes new options.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The configured options value
+    pub fn new(value: T) -> Self {
+        Self { value }
+    }
+
+    /// Gets the configured options value.
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> Deref for Options<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+/// Represents configured options that are created once per scope.
+pub struct OptionsSnapshot<T> {
+    value: T,
+}
+
+impl<T> OptionsSnapshot<T> {
+    /// Initializes a new options snapshot.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The configured options value
+    pub fn new(value: T) -> Self {
+        Self { value }
+    }
+
+    /// Gets the configured options value.
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> Deref for OptionsSnapshot<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+/// Represents an action that configures options.
+pub struct ConfigureOptions<T> {
+    action: Box<dyn Fn(&mut T)>,
+}
+
+impl<T> ConfigureOptions<T> {
+    /// Initializes a new options configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `action` - The action that configures the options
+    pub fn new<F: Fn(&mut T) + 'static>(action: F) -> Self {
+        Self {
+            action: Box::new(action),
+        }
+    }
+
+    /// Configures the specified options.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - The options to configure
+    pub fn configure(&self, options: &mut T) {
+        (self.action)(options)
+    }
+}
+
+/// Represents an action that configures options after all other configurations.
+pub struct PostConfigureOptions<T> {
+    action: Box<dyn Fn(&mut T)>,
+}
+
+impl<T> PostConfigureOptions<T> {
+    /// Initializes a new options post-configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `action` - The action that configures the options
+    pub fn new<F: Fn(&mut T) + 'static>(action: F) -> Self {
+        Self {
+            action: Box::new(action),
+        }
+    }
+
+    /// Configures the specified options.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - The options to configure
+    pub fn post_configure(&self, options: &mut T) {
+        (self.action)(options)
+    }
+}
+
+/// Represents the callback function used to validate options.
+pub type OptionsValidator<T> = dyn Fn(&T) -> Result<(), String>;
+
+/// Represents a validation of configured options.
+pub struct ValidateOptions<T> {
+    validate: Box<OptionsValidator<T>>,
+}
+
+impl<T> ValidateOptions<T> {
+    /// Initializes a new options validation.
+    ///
+    /// # Arguments
+    ///
+    /// * `validate` - The function that validates the options and returns an error message when they are invalid
+    pub fn new<F: Fn(&T) -> Result<(), String> + 'static>(validate: F) -> Self {
+        Self {
+            validate: Box::new(validate),
+        }
+    }
+
+    /// Validates the specified options.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - The options to validate
+    pub fn validate(&self, options: &T) -> Result<(), String> {
+        (self.validate)(options)
+    }
+}
+
+// configurations are applied in the order they were registered, followed by all
+// post-configurations in the order they were registered
+fn configure<T: Default + 'static>(services: &ServiceProvider) -> T {
+    let mut options = T::default();
+
+    for configuration in services.get_all::<ConfigureOptions<T>>() {
+        configuration.configure(&mut options);
+    }
+
+    for configuration in services.get_all::<PostConfigureOptions<T>>() {
+        configuration.post_configure(&mut options);
+    }
+
+    options
+}
+
+fn check<T: 'static>(services: &ServiceProvider, options: &T) -> Result<(), String> {
+    let errors: Vec<_> = services
+        .get_all::<ValidateOptions<T>>()
+        .filter_map(|validation| validation.validate(options).err())
+        .collect();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "The options of type '{}' are invalid. {}",
+            type_name::<T>(),
+            errors.join(" ")
+        ))
+    }
+}
+
+fn create<T: Default + 'static>(services: &ServiceProvider) -> T {
+    let options = configure::<T>(services);
+
+    if let Err(error) = check(services, &options) {
+        panic!("{}", error);
+    }
+
+    options
+}
+
+impl ServiceCollection {
+    /// Adds [options](struct.Options.html) and an [options snapshot](struct.OptionsSnapshot.html)
+    /// of the specified type if they have not already been added.
+    pub fn add_options<T: Default + 'static>(&mut self) -> &mut Self {
+        self.try_add(
+            singleton_as_self::<Options<T>>()
+                .depends_on(zero_or_more::<ConfigureOptions<T>>())
+                .depends_on(zero_or_more::<PostConfigureOptions<T>>())
+                .depends_on(zero_or_more::<ValidateOptions<T>>())
+                .from(|sp| ServiceRef::new(Options::new(create::<T>(sp)))),
+        )
+        .try_add(
+            ServiceDescriptorBuilder::<OptionsSnapshot<T>, OptionsSnapshot<T>>::new(
+                ServiceLifetime::Scoped,
+                Type::of::<OptionsSnapshot<T>>(),
+            )
+            .depends_on(zero_or_more::<ConfigureOptions<T>>())
+            .depends_on(zero_or_more::<PostConfigureOptions<T>>())
+            .depends_on(zero_or_more::<ValidateOptions<T>>())
+            .from(|sp| ServiceRef::new(OptionsSnapshot::new(create::<T>(sp)))),
+        )
+    }
+
+    /// Adds an action that configures options of the specified type.
+    ///
+    /// # Arguments
+    ///
+    /// * `action` - The action that configures the options
+    pub fn configure<T, F>(&mut self, action: F) -> &mut Self
+    where
+        T: Default + 'static,
+        F: Fn(&mut T) + 'static,
+    {
+        self.add(existing_as_self(ConfigureOptions::new(action)))
+            .add_options::<T>()
+    }
+
+    /// Adds an action that configures options of the specified type after all other configurations.
+    ///
+    /// # Arguments
+    ///
+    /// * `action` - The action that configures the options
+    pub fn post_configure<T, F>(&mut self, action: F) -> &mut Self
+    where
+        T: Default + 'static,
+        F: Fn(&mut T) + 'static,
+    {
+        self.add(existing_as_self(PostConfigureOptions::new(action)))
+            .add_options::<T>()
+    }
+
+    /// Adds a validation for options of the specified type.
+    ///
+    /// # Arguments
+    ///
+    /// * `validate` - The function that validates the options and returns an error message when they are invalid
+    ///
+    /// # Remarks
+    ///
+    /// The options are validated when a [service provider](struct.ServiceProvider.html) is built,
+    /// which fails with a [validation error](struct.ValidationError.html) that contains all of the
+    /// validation error messages when the options are invalid.
+    pub fn validate_options<T, F>(&mut self, validate: F) -> &mut Self
+    where
+        T: Default + 'static,
+        F: Fn(&T) -> Result<(), String> + 'static,
+    {
+        let options = Type::of::<T>();
+
+        if !self.validations.iter().any(|(type_, _)| *type_ == options) {
+            self.validations
+                .push((options, Box::new(|sp| check::<T>(sp, &configure::<T>(sp)))));
+        }
+
+        self.add(existing_as_self(ValidateOptions::new(validate)))
+            .add_options::<T>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{options::*, *};
+
+    #[derive(Default)]
+    struct ServerOptions {
+        host: String,
+        port: u16,
+    }
+
+    #[test]
+    fn options_should_apply_configurations_in_order() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .post_configure(|o: &mut ServerOptions| o.host.push_str(".local"))
+            .configure(|o: &mut ServerOptions| o.host = "localhost".into())
+            .configure(|o: &mut ServerOptions| o.port = 80)
+            .configure(|o: &mut ServerOptions| o.port += 8000)
+            .build_provider()
+            .unwrap();
+
+        // act
+        let options = provider.get_required::<Options<ServerOptions>>();
+
+        // assert
+        assert_eq!(&options.host, "localhost.local");
+        assert_eq!(options.port, 8080);
+    }
+
+    #[test]
+    #[allow(ambiguous_wide_pointer_comparisons)]
+    fn options_snapshot_should_be_created_once_per_scope() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .configure(|o: &mut ServerOptions| o.port = 8080)
+            .build_provider()
+            .unwrap();
+        let scope1 = provider.create_scope();
+        let scope2 = provider.create_scope();
+
+        // act
+        let snapshot1 = scope1.get_required::<OptionsSnapshot<ServerOptions>>();
+        let snapshot2 = scope2.get_required::<OptionsSnapshot<ServerOptions>>();
+
+        // assert
+        assert_eq!(snapshot1.port, 8080);
+        assert!(ServiceRef::ptr_eq(
+            &snapshot1,
+            &scope1.get_required::<OptionsSnapshot<ServerOptions>>()
+        ));
+        assert!(!ServiceRef::ptr_eq(&snapshot1, &snapshot2));
+    }
+
+    #[test]
+    fn build_provider_should_report_invalid_options() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services
+            .validate_options(|o: &ServerOptions| {
+                if o.host.is_empty() {
+                    Err("A host is required.".into())
+                } else {
+                    Ok(())
+                }
+            })
+            .validate_options(|o: &ServerOptions| {
+                if o.port == 0 {
+                    Err("A port is required.".into())
+                } else {
+                    Ok(())
+                }
+            });
+
+        // act
+        let result = services.build_provider();
+
+        // assert
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            format!(
+                "The options of type '{}' are invalid. A host is required. A port is required.",
+                std::any::type_name::<ServerOptions>()
+            )
+        );
+    }
+
+    #[test]
+    fn build_provider_should_validate_configured_options() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services
+            .configure(|o: &mut ServerOptions| o.port = 8080)
+            .validate_options(|o: &ServerOptions| {
+                if o.port == 0 {
+                    Err("A port is required.".into())
+                } else {
+                    Ok(())
+                }
+            });
+
+        // act
+        let provider = services.build_provider().unwrap();
+
+        // assert
+        assert_eq!(provider.get_required::<Options<ServerOptions>>().port, 8080);
+    }
+}
@@ -0,0 +1,41 @@
+use crate::ServiceDescriptor;
+
+// returns true if the descriptor defines an order or a relative constraint
+pub(crate) fn is_ordered(descriptor: &ServiceDescriptor) -> bool {
+    descriptor.order() != 0 || !descriptor.before().is_empty() || !descriptor.after().is_empty()
+}
+
+fn precedes(first: &ServiceDescriptor, second: &ServiceDescriptor) -> bool {
+    first.before().contains(second.implementation_type())
+        || second.after().contains(first.implementation_type())
+}
+
+// sorts the descriptors of a single service type by their order, which is stable, and then
+// by their relative constraints. the positions of the sorted descriptors are returned along
+// with the positions of any descriptors whose constraints conflict, in order.
+pub(crate) fn sort(descriptors: &[&ServiceDescriptor]) -> (Vec<usize>, Vec<usize>) {
+    let mut pending: Vec<_> = (0..descriptors.len()).collect();
+
+    pending.sort_by_key(|i| descriptors[*i].order());
+
+    if descriptors
+        .iter()
+        .all(|d| d.before().is_empty() && d.after().is_empty())
+    {
+        return (pending, Vec::with_capacity(0));
+    }
+
+    let mut sorted = Vec::with_capacity(pending.len());
+
+    // the next descriptor is the first pending descriptor that no other pending
+    // descriptor must precede. if there isn't one, the remaining constraints conflict.
+    while let Some(position) = pending.iter().position(|i| {
+        !pending
+            .iter()
+            .any(|j| j != i && precedes(descriptors[*j], descriptors[*i]))
+    }) {
+        sorted.push(pending.remove(position));
+    }
+
+    (sorted, pending)
+}
@@ -0,0 +1,80 @@
+use crate::{ServiceProvider, ServiceRef};
+
+/// Represents the callback function used to create a service with a runtime argument.
+pub type ParamServiceFactory<A, T> = dyn Fn(&ServiceProvider, A) -> ServiceRef<T>;
+
+/// Represents a factory that creates a service from injected services and a runtime argument.
+///
+/// # Remarks
+///
+/// Multiple runtime arguments are represented as a tuple. A parameterized factory
+/// resolves services from the [service provider](struct.ServiceProvider.html) that
+/// resolved the factory itself; therefore, it should only be registered with a
+/// transient or scoped lifetime.
+pub struct ParamFactory<A, T: ?Sized> {
+    services: ServiceProvider,
+    factory: ServiceRef<ParamServiceFactory<A, T>>,
+}
+
+impl<A, T: ?Sized> ParamFactory<A, T> {
+    /// Initializes a new parameterized factory.
+    ///
+    /// # Arguments
+    ///
+    /// * `services` - The [service provider](struct.ServiceProvider.html) used to resolve services
+    /// * `factory` - The factory method used to create the service
+    pub fn new(services: ServiceProvider, factory: ServiceRef<ParamServiceFactory<A, T>>) -> Self {
+        Self { services, factory }
+    }
+
+    /// Creates and returns a new service using the specified argument.
+    ///
+    /// # Arguments
+    ///
+    /// * `arg` - The runtime argument used to create the service
+    pub fn create(&self, arg: A) -> ServiceRef<T> {
+        (self.factory)(&self.services, arg)
+    }
+}
+
+impl<A, T: ?Sized> Clone for ParamFactory<A, T> {
+    fn clone(&self) -> Self {
+        Self::new(self.services.clone(), self.factory.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{test::*, *};
+
+    struct Tenant {
+        id: String,
+        service: ServiceRef<dyn TestService>,
+    }
+
+    #[test]
+    fn param_factory_should_create_service_with_argument() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add(
+                transient::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl { value: 42 })),
+            )
+            .add(param_factory(|sp, id: &str| {
+                ServiceRef::new(Tenant {
+                    id: id.to_owned(),
+                    service: sp.get_required::<dyn TestService>(),
+                })
+            }))
+            .build_provider()
+            .unwrap();
+        let factory = provider.get_required::<ParamFactory<&str, Tenant>>();
+
+        // act
+        let tenant = factory.create("acme");
+
+        // assert
+        assert_eq!(&tenant.id, "acme");
+        assert_eq!(tenant.service.value(), 42);
+    }
+}
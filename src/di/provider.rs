@@ -1,13 +1,228 @@
-use crate::{ServiceDescriptor, ServiceRef, Type};
+use crate::{
+    ordering::{is_ordered, sort},
+    tenant::Tenant,
+    thread, ConditionOutcome, Interception, Interceptor, ResolutionObserver, ServiceCollection,
+    ServiceDescriptor, ServiceHandle, ServiceLifetime, ServiceRef, TenantProvider, Type,
+    ValidationError,
+};
+use spin::Once;
 use std::any::{type_name, Any};
 use std::collections::HashMap;
-use std::iter::empty;
 use std::marker::PhantomData;
+use std::ops::Deref;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+static NEXT_SCOPE_ID: AtomicUsize = AtomicUsize::new(1);
+
+// the pipeline of a child provider starts with the pipeline of its parent. an inherited
+// service is resolved through the pipeline of the parent by the parent, so the child only
+// applies the extensions it registered itself.
+struct Pipeline {
+    observers: Vec<ServiceRef<dyn ResolutionObserver>>,
+    interceptors: Vec<ServiceRef<dyn Interceptor>>,
+    inherited_observers: usize,
+    inherited_interceptors: usize,
+}
+
+impl Pipeline {
+    fn observers(&self, descriptor: &ServiceDescriptor) -> &[ServiceRef<dyn ResolutionObserver>] {
+        if descriptor.is_inherited() {
+            &self.observers[self.inherited_observers..]
+        } else {
+            &self.observers
+        }
+    }
+
+    fn interceptors(&self, descriptor: &ServiceDescriptor) -> &[ServiceRef<dyn Interceptor>] {
+        if descriptor.is_inherited() {
+            &self.interceptors[self.inherited_interceptors..]
+        } else {
+            &self.interceptors
+        }
+    }
+}
+
+// the descriptors are shared, immutable, and addressed by slot. the slots of each
+// service type are in registration order so that the last registration is resolved by default.
+// when the descriptors of a service type define an order, the ordered slots are kept separately
+// and are only used to enumerate all of the services of that type.
+// scoped descriptors are additionally assigned a slot in the instance cache of each scope
+// and tenant descriptors are assigned a slot in the instance cache of each tenant.
+pub(crate) struct Registry {
+    descriptors: Vec<ServiceDescriptor>,
+    slots: HashMap<Type, Vec<usize>>,
+    ordered: HashMap<Type, Vec<usize>>,
+    scoped: usize,
+    tenanted: usize,
+    tenants: Mutex<HashMap<String, ServiceRef<Tenant>>>,
+    conditions: Vec<ConditionOutcome>,
+}
+
+// the descriptors of a registry are shared by every scope in the same way as the service provider
+#[cfg(feature = "async")]
+unsafe impl Send for Registry {}
+
+#[cfg(feature = "async")]
+unsafe impl Sync for Registry {}
+
+impl Registry {
+    fn new(mut descriptors: Vec<ServiceDescriptor>, conditions: Vec<ConditionOutcome>) -> Self {
+        let mut slots = HashMap::with_capacity(descriptors.len());
+        let mut scoped = 0;
+        let mut tenanted = 0;
+
+        for (slot, descriptor) in descriptors.iter_mut().enumerate() {
+            if descriptor.lifetime() == ServiceLifetime::Scoped {
+                descriptor.set_cache_slot(scoped);
+                scoped += 1;
+            } else if descriptor.lifetime() == ServiceLifetime::Tenant {
+                descriptor.set_cache_slot(tenanted);
+                tenanted += 1;
+            }
+
+            slots
+                .entry(descriptor.service_type().clone())
+                .or_insert_with(Vec::new)
+                .push(slot);
+        }
+
+        let mut ordered = HashMap::new();
+
+        for (service_type, values) in slots.iter_mut() {
+            if values.len() > 1 && values.iter().any(|slot| is_ordered(&descriptors[*slot])) {
+                let group: Vec<_> = values.iter().map(|slot| &descriptors[*slot]).collect();
+                let (sorted, conflicts) = sort(&group);
+                let sorted = sorted
+                    .into_iter()
+                    .chain(conflicts)
+                    .map(|i| values[i])
+                    .collect();
+                ordered.insert(service_type.clone(), sorted);
+            }
+
+            values.shrink_to_fit();
+        }
+
+        descriptors.shrink_to_fit();
+        slots.shrink_to_fit();
+
+        Self {
+            descriptors,
+            slots,
+            ordered,
+            scoped,
+            tenanted,
+            tenants: Mutex::new(HashMap::new()),
+            conditions,
+        }
+    }
+
+    fn slots(&self, service_type: &Type) -> &[usize] {
+        self.slots.get(service_type).map_or(&[], |slots| slots)
+    }
+
+    fn last(&self, service_type: &Type) -> Option<&ServiceDescriptor> {
+        self.slots(service_type)
+            .last()
+            .map(|slot| &self.descriptors[*slot])
+    }
+
+    pub(crate) fn descriptor(&self, slot: usize) -> &ServiceDescriptor {
+        &self.descriptors[slot]
+    }
+
+    fn all<'a>(&'a self, service_type: &Type) -> impl Iterator<Item = &'a ServiceDescriptor> {
+        self.ordered
+            .get(service_type)
+            .map_or_else(|| self.slots(service_type), Vec::as_slice)
+            .iter()
+            .map(move |slot| &self.descriptors[*slot])
+    }
+}
+
+// the per-thread instances of the current thread are dropped with the registry that owns them
+impl Drop for Registry {
+    fn drop(&mut self) {
+        thread::remove(
+            self.descriptors
+                .iter()
+                .filter(|descriptor| descriptor.lifetime() == ServiceLifetime::PerThread)
+                .map(ServiceDescriptor::cache_key),
+        );
+    }
+}
+
+// the cached instance of a service. when the async feature is enabled, an instance is shared
+// across threads in the same way as the service provider that owns it.
+pub(crate) struct Instance(Once<ServiceRef<dyn Any>>);
+
+impl Instance {
+    pub(crate) fn new(value: Once<ServiceRef<dyn Any>>) -> Self {
+        Self(value)
+    }
+}
+
+impl Default for Instance {
+    fn default() -> Self {
+        Self(Once::new())
+    }
+}
+
+impl Deref for Instance {
+    type Target = Once<ServiceRef<dyn Any>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[cfg(feature = "async")]
+unsafe impl Send for Instance {}
+
+#[cfg(feature = "async")]
+unsafe impl Sync for Instance {}
+
+// a child provider resolves inherited services from its parent. each scope of a
+// child provider is paired with a scope of the parent provider. a scope created
+// for a tenant and every scope created from it share the instances of the tenant.
+struct Scope {
+    id: usize,
+    pipeline: ServiceRef<Pipeline>,
+    instances: Once<Box<[Instance]>>,
+    parent: Option<ServiceProvider>,
+    tenant: Option<ServiceRef<Tenant>>,
+}
+
+impl Scope {
+    fn new(
+        pipeline: ServiceRef<Pipeline>,
+        parent: Option<ServiceProvider>,
+        tenant: Option<ServiceRef<Tenant>>,
+    ) -> Self {
+        Self {
+            id: NEXT_SCOPE_ID.fetch_add(1, Ordering::Relaxed),
+            pipeline,
+            instances: Once::new(),
+            parent,
+            tenant,
+        }
+    }
+}
+
+impl Drop for Scope {
+    fn drop(&mut self) {
+        for observer in &self.pipeline.observers {
+            observer.on_scope_dropped(self.id);
+        }
+    }
+}
 
 /// Represents a service provider.
 #[derive(Clone)]
 pub struct ServiceProvider {
-    services: ServiceRef<HashMap<Type, Vec<ServiceDescriptor>>>,
+    registry: ServiceRef<Registry>,
+    scope: ServiceRef<Scope>,
 }
 
 #[cfg(feature = "async")]
@@ -23,61 +238,467 @@ impl ServiceProvider {
     ///
     /// * `services` - The map of services descriptors encapsulated by the provider.
     pub fn new(services: HashMap<Type, Vec<ServiceDescriptor>>) -> Self {
+        Self::with_observers(services, Vec::with_capacity(0))
+    }
+
+    /// Initializes a new service provider with resolution observers.
+    ///
+    /// # Arguments
+    ///
+    /// * `services` - The map of services descriptors encapsulated by the provider.
+    /// * `observers` - The [observers](trait.ResolutionObserver.html) notified when services are resolved.
+    pub fn with_observers(
+        services: HashMap<Type, Vec<ServiceDescriptor>>,
+        observers: Vec<ServiceRef<dyn ResolutionObserver>>,
+    ) -> Self {
+        let services = services.into_values().flatten().collect();
+        Self::with_pipeline(
+            services,
+            observers,
+            Vec::with_capacity(0),
+            Vec::with_capacity(0),
+            None,
+        )
+    }
+
+    pub(crate) fn with_pipeline(
+        services: Vec<ServiceDescriptor>,
+        observers: Vec<ServiceRef<dyn ResolutionObserver>>,
+        interceptors: Vec<ServiceRef<dyn Interceptor>>,
+        conditions: Vec<ConditionOutcome>,
+        parent: Option<ServiceProvider>,
+    ) -> Self {
+        let pipeline = match &parent {
+            Some(parent) => {
+                let inherited = &parent.scope.pipeline;
+                Pipeline {
+                    observers: inherited
+                        .observers
+                        .iter()
+                        .cloned()
+                        .chain(observers)
+                        .collect(),
+                    interceptors: inherited
+                        .interceptors
+                        .iter()
+                        .cloned()
+                        .chain(interceptors)
+                        .collect(),
+                    inherited_observers: inherited.observers.len(),
+                    inherited_interceptors: inherited.interceptors.len(),
+                }
+            }
+            _ => Pipeline {
+                observers,
+                interceptors,
+                inherited_observers: 0,
+                inherited_interceptors: 0,
+            },
+        };
+
         Self {
-            services: ServiceRef::new(services),
+            registry: ServiceRef::new(Registry::new(services, conditions)),
+            scope: ServiceRef::new(Scope::new(ServiceRef::new(pipeline), parent, None)),
         }
     }
 
+    /// Gets the identifier of the scope the service provider resolves services from.
+    pub fn scope_id(&self) -> usize {
+        self.scope.id
+    }
+
     /// Gets a service of the specified type.
     pub fn get<T: Any + ?Sized>(&self) -> Option<ServiceRef<T>> {
+        self.resolve_last(&Type::of::<T>())
+            .map(|service| service.downcast_ref::<ServiceRef<T>>().unwrap().clone())
+    }
+
+    /// Gets all of the services of the specified type.
+    ///
+    /// # Remarks
+    ///
+    /// Services denied by an [interceptor](trait.Interceptor.html) are not included.
+    pub fn get_all<T: Any + ?Sized>(&self) -> impl Iterator<Item = ServiceRef<T>> + '_ {
         let key = Type::of::<T>();
 
-        if let Some(descriptors) = self.services.get(&key) {
-            if let Some(descriptor) = descriptors.last() {
-                return Some(
-                    descriptor
-                        .get(self)
-                        .downcast_ref::<ServiceRef<T>>()
-                        .unwrap()
-                        .clone(),
-                );
-            }
-        }
+        ServiceIterator::new(self, self.registry.all(&key))
+    }
 
-        None
+    /// Gets all of the services of the specified type that have the specified tag.
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - The tag of the services to get
+    pub fn get_all_tagged<T: Any + ?Sized>(
+        &self,
+        tag: &str,
+    ) -> impl Iterator<Item = ServiceRef<T>> + '_ {
+        let key = Type::of::<T>();
+        let tag = tag.to_owned();
+
+        ServiceIterator::new(
+            self,
+            self.registry
+                .all(&key)
+                .filter(move |descriptor| descriptor.has_tag(&tag)),
+        )
     }
 
-    /// Gets all of the services of the specified type.
-    pub fn get_all<T: Any + ?Sized>(&self) -> impl Iterator<Item = ServiceRef<T>> + '_ {
+    /// Gets all of the services of the specified type that have metadata of the specified type.
+    ///
+    /// # Remarks
+    ///
+    /// Each service is paired with its metadata. Services without metadata of type `M` are skipped.
+    pub fn get_all_with_metadata<T: Any + ?Sized, M: Any>(
+        &self,
+    ) -> impl Iterator<Item = (ServiceRef<T>, &M)> + '_ {
         let key = Type::of::<T>();
 
-        if let Some(descriptors) = self.services.get(&key) {
-            ServiceIterator::new(self, descriptors.iter())
-        } else {
-            ServiceIterator::new(self, empty())
-        }
+        self.registry.all(&key).filter_map(move |descriptor| {
+            let metadata = descriptor.metadata::<M>()?;
+            let service = self.resolve(descriptor)?;
+            Some((
+                service.downcast_ref::<ServiceRef<T>>().unwrap().clone(),
+                metadata,
+            ))
+        })
     }
 
     /// Gets a required service of the specified type.
     ///
     /// # Panics
     ///
-    /// The requested service of type `T` does not exist.
+    /// The requested service of type `T` does not exist, has a [tenant lifetime](enum.ServiceLifetime.html#variant.Tenant)
+    /// and is not resolved from a [tenant provider](struct.TenantProvider.html), or was denied by an
+    /// [interceptor](trait.Interceptor.html).
     pub fn get_required<T: Any + ?Sized>(&self) -> ServiceRef<T> {
         if let Some(service) = self.get::<T>() {
             service
         } else {
-            panic!(
-                "No service for type '{}' has been registered.",
-                type_name::<T>()
-            );
+            match self.registry.last(&Type::of::<T>()) {
+                Some(descriptor) if self.requires_tenant(descriptor) => panic!(
+                    "Service type '{}' has a tenant lifetime and must be resolved from a tenant provider.",
+                    type_name::<T>()
+                ),
+                Some(_) => panic!(
+                    "Resolution of service type '{}' was denied.",
+                    type_name::<T>()
+                ),
+                _ => panic!(
+                    "No service for type '{}' has been registered.",
+                    type_name::<T>()
+                ),
+            }
         }
     }
 
+    /// Gets a service of the specified type.
+    ///
+    /// # Arguments
+    ///
+    /// * `service_type` - The [type](struct.Type.html) of service to get
+    ///
+    /// # Remarks
+    ///
+    /// The returned service is a `ServiceRef<dyn Any>` that contains a `ServiceRef<T>`
+    /// of the requested service type.
+    pub fn get_by_type(&self, service_type: &Type) -> Option<ServiceRef<dyn Any>> {
+        self.resolve_last(service_type)
+    }
+
+    /// Gets all of the services of the specified type.
+    ///
+    /// # Arguments
+    ///
+    /// * `service_type` - The [type](struct.Type.html) of service to get
+    ///
+    /// # Remarks
+    ///
+    /// Each returned service is a `ServiceRef<dyn Any>` that contains a `ServiceRef<T>`
+    /// of the requested service type.
+    pub fn get_all_by_type<'a>(
+        &'a self,
+        service_type: &Type,
+    ) -> impl Iterator<Item = ServiceRef<dyn Any>> + 'a {
+        self.registry
+            .all(service_type)
+            .filter_map(move |descriptor| self.resolve(descriptor))
+    }
+
+    /// Returns true if a service of the specified type has been registered.
+    ///
+    /// # Arguments
+    ///
+    /// * `service_type` - The [type](struct.Type.html) of service to find
+    pub fn contains(&self, service_type: &Type) -> bool {
+        self.registry.slots.contains_key(service_type)
+    }
+
+    /// Returns true if a service of the specified type has been registered.
+    ///
+    /// # Remarks
+    ///
+    /// The service is not instantiated.
+    pub fn is_registered<T: Any + ?Sized>(&self) -> bool {
+        self.contains(&Type::of::<T>())
+    }
+
+    /// Gets the number of services registered for the specified type.
+    ///
+    /// # Remarks
+    ///
+    /// None of the services are instantiated.
+    pub fn count<T: Any + ?Sized>(&self) -> usize {
+        self.registry.slots(&Type::of::<T>()).len()
+    }
+
+    /// Gets the [service descriptor](struct.ServiceDescriptor.html) used to resolve a service of the specified type, if any.
+    ///
+    /// # Remarks
+    ///
+    /// The service is not instantiated. When multiple services are registered for the type, the last
+    /// descriptor is returned, which is the same descriptor used by [get](#method.get).
+    pub fn descriptor<T: Any + ?Sized>(&self) -> Option<&ServiceDescriptor> {
+        self.registry.last(&Type::of::<T>())
+    }
+
+    /// Gets a handle that resolves a service of the specified type without looking up the type again, if any.
+    ///
+    /// # Remarks
+    ///
+    /// When multiple services are registered for the type, the handle resolves the same service as [get](#method.get).
+    pub fn handle<T: Any + ?Sized>(&self) -> Option<ServiceHandle<T>> {
+        self.registry
+            .slots(&Type::of::<T>())
+            .last()
+            .map(|slot| ServiceHandle::new(self.registry.clone(), *slot))
+    }
+
+    /// Gets all of the [service descriptors](struct.ServiceDescriptor.html) registered in the provider.
+    ///
+    /// # Remarks
+    ///
+    /// The order of the descriptors is unspecified.
+    pub fn descriptors(&self) -> impl Iterator<Item = &ServiceDescriptor> {
+        self.registry.descriptors.iter()
+    }
+
+    /// Gets the outcomes of the conditional registrations evaluated when the service provider was built.
+    pub fn conditions(&self) -> &[ConditionOutcome] {
+        &self.registry.conditions
+    }
+
     /// Creates and returns a new service provider that is used to resolve
     /// services from a newly create scope.
+    ///
+    /// # Remarks
+    ///
+    /// The service descriptors are shared by every scope. The instances of scoped
+    /// services are not allocated until a scoped service is first resolved.
     pub fn create_scope(&self) -> Self {
-        Self::new(self.services.as_ref().clone())
+        let scope = Self {
+            registry: self.registry.clone(),
+            scope: ServiceRef::new(Scope::new(
+                self.scope.pipeline.clone(),
+                self.scope.parent.as_ref().map(Self::create_scope),
+                self.scope.tenant.clone(),
+            )),
+        };
+
+        self.notify(|observer| observer.on_scope_created(scope.scope.id, self.scope.id));
+        scope
+    }
+
+    /// Creates and returns a new child service provider that overlays registrations on the service provider.
+    ///
+    /// # Arguments
+    ///
+    /// * `configure` - The function used to register the services of the child service provider
+    ///
+    /// # Remarks
+    ///
+    /// A service type registered by the child replaces all registrations of the same service type in the
+    /// parent. Services of any other type are resolved by the parent, which shares its singletons with the
+    /// child. The child owns the instances of the services it registers. The registrations of the child are
+    /// validated against the combined registrations of the child and the parent. Each scope created by the
+    /// child is paired with a new scope of the parent. The interceptors and observers of the parent
+    /// are applied to the services registered by the child before those of the child.
+    pub fn create_child<F>(&self, configure: F) -> Result<Self, ValidationError>
+    where
+        F: FnOnce(&mut ServiceCollection),
+    {
+        let mut services = ServiceCollection::new();
+        configure(&mut services);
+        services.build_child_provider(self)
+    }
+
+    /// Creates and returns a new [tenant provider](struct.TenantProvider.html) for the specified tenant.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key that identifies the tenant
+    ///
+    /// # Remarks
+    ///
+    /// The instances of services with a [tenant lifetime](enum.ServiceLifetime.html#variant.Tenant)
+    /// are created once for each key and shared by every tenant provider created for the same key
+    /// until the tenant is [removed](#method.remove_tenant).
+    pub fn tenant(&self, key: &str) -> TenantProvider {
+        let tenant = self
+            .registry
+            .tenants
+            .lock()
+            .unwrap()
+            .entry(key.to_owned())
+            .or_insert_with(|| ServiceRef::new(Tenant::new(key, self.registry.tenanted)))
+            .clone();
+        let provider = Self {
+            registry: self.registry.clone(),
+            scope: ServiceRef::new(Scope::new(
+                self.scope.pipeline.clone(),
+                self.scope.parent.as_ref().map(|p| p.tenant(key).provider),
+                Some(tenant),
+            )),
+        };
+
+        self.notify(|observer| observer.on_scope_created(provider.scope.id, self.scope.id));
+        TenantProvider::new(provider)
+    }
+
+    /// Gets the key of the tenant the service provider resolves services for, if any.
+    pub fn tenant_key(&self) -> Option<&str> {
+        self.scope.tenant.as_ref().map(|tenant| tenant.key.as_str())
+    }
+
+    /// Removes the instances of the specified tenant.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key that identifies the tenant
+    ///
+    /// # Remarks
+    ///
+    /// Existing tenant providers for the tenant continue to use the removed instances. The next
+    /// tenant provider created for the same key creates new instances. Returns true if the tenant
+    /// was removed; otherwise, false.
+    pub fn remove_tenant(&self, key: &str) -> bool {
+        self.registry.tenants.lock().unwrap().remove(key).is_some()
+    }
+
+    pub(crate) fn tenant_instance(&self, slot: usize) -> Option<&Instance> {
+        self.scope
+            .tenant
+            .as_ref()
+            .map(|tenant| &tenant.instances[slot])
+    }
+
+    pub(crate) fn resolve_inherited(&self, slot: usize) -> ServiceRef<dyn Any> {
+        let parent = self.scope.parent.as_ref().unwrap();
+        let descriptor = &parent.registry.descriptors[slot];
+
+        match parent.resolve(descriptor) {
+            Some(service) => service,
+            _ => panic!(
+                "Resolution of service type '{}' was denied.",
+                descriptor.service_type()
+            ),
+        }
+    }
+
+    pub(crate) fn scoped_instance(&self, slot: usize) -> Option<&Instance> {
+        let count = self.registry.scoped;
+        let instances = self
+            .scope
+            .instances
+            .call_once(|| (0..count).map(|_| Instance::default()).collect());
+
+        instances.get(slot)
+    }
+
+    pub(crate) fn is_observed(&self, descriptor: &ServiceDescriptor) -> bool {
+        !self.scope.pipeline.observers(descriptor).is_empty()
+    }
+
+    pub(crate) fn notify<F: Fn(&dyn ResolutionObserver)>(&self, event: F) {
+        for observer in &self.scope.pipeline.observers {
+            event(observer.as_ref());
+        }
+    }
+
+    pub(crate) fn notify_for<F: Fn(&dyn ResolutionObserver)>(
+        &self,
+        descriptor: &ServiceDescriptor,
+        event: F,
+    ) {
+        for observer in self.scope.pipeline.observers(descriptor) {
+            event(observer.as_ref());
+        }
+    }
+
+    pub(crate) fn post_process(
+        &self,
+        descriptor: &ServiceDescriptor,
+        mut instance: ServiceRef<dyn Any>,
+    ) -> ServiceRef<dyn Any> {
+        for interceptor in self.scope.pipeline.interceptors(descriptor) {
+            instance = interceptor.created(descriptor, self, instance);
+        }
+
+        instance
+    }
+
+    pub(crate) fn resolve_slot(
+        &self,
+        registry: &ServiceRef<Registry>,
+        slot: usize,
+    ) -> Option<ServiceRef<dyn Any>> {
+        if !ServiceRef::ptr_eq(&self.registry, registry) {
+            panic!(
+                "The service handle was not created by this service provider or one of its scopes."
+            );
+        }
+
+        self.resolve(&self.registry.descriptors[slot])
+    }
+
+    fn resolve_last(&self, service_type: &Type) -> Option<ServiceRef<dyn Any>> {
+        if let Some(descriptor) = self.registry.last(service_type) {
+            return self.resolve(descriptor);
+        }
+
+        self.notify(|observer| observer.on_failed(service_type, self.scope.id));
+        None
+    }
+
+    // a service with a tenant lifetime can only be resolved for a tenant
+    pub(crate) fn requires_tenant(&self, descriptor: &ServiceDescriptor) -> bool {
+        descriptor.lifetime() == ServiceLifetime::Tenant && self.scope.tenant.is_none()
+    }
+
+    fn resolve(&self, descriptor: &ServiceDescriptor) -> Option<ServiceRef<dyn Any>> {
+        if self.requires_tenant(descriptor) {
+            let service_type = descriptor.service_type();
+            self.notify_for(descriptor, |observer| {
+                observer.on_failed(service_type, self.scope.id)
+            });
+            return None;
+        }
+
+        for interceptor in self.scope.pipeline.interceptors(descriptor) {
+            match interceptor.intercept(descriptor, self) {
+                Interception::Proceed => continue,
+                Interception::Replace(instance) => return Some(instance),
+                Interception::Deny => {
+                    let service_type = descriptor.service_type();
+                    self.notify_for(descriptor, |observer| {
+                        observer.on_failed(service_type, self.scope.id)
+                    });
+                    return None;
+                }
+            }
+        }
+
+        Some(descriptor.get(self))
     }
 }
 
@@ -106,25 +727,25 @@ impl<'a, T: Any + ?Sized> ServiceIterator<'a, T> {
 impl<'a, T: Any + ?Sized> Iterator for ServiceIterator<'a, T> {
     type Item = ServiceRef<T>;
     fn next(&mut self) -> Option<Self::Item> {
-        if let Some(descriptor) = self.descriptors.next() {
-            Some(
-                descriptor
-                    .get(self.provider)
-                    .downcast_ref::<ServiceRef<T>>()
-                    .unwrap()
-                    .clone(),
-            )
-        } else {
-            None
+        for descriptor in self.descriptors.by_ref() {
+            if let Some(service) = self.provider.resolve(descriptor) {
+                return Some(service.downcast_ref::<ServiceRef<T>>().unwrap().clone());
+            }
         }
+
+        None
     }
 }
 
 impl Default for ServiceProvider {
     fn default() -> Self {
-        Self {
-            services: ServiceRef::new(HashMap::with_capacity(0)),
-        }
+        Self::with_pipeline(
+            Vec::with_capacity(0),
+            Vec::with_capacity(0),
+            Vec::with_capacity(0),
+            Vec::with_capacity(0),
+            None,
+        )
     }
 }
 
@@ -132,11 +753,15 @@ impl Default for ServiceProvider {
 mod tests {
 
     use crate::{test::*, *};
+    use std::any::Any;
     use std::fs::remove_file;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
     use std::path::{Path, PathBuf};
+    use std::sync::Mutex;
+    use std::time::Duration;
 
     #[cfg(feature = "async")]
-    use std::sync::{Arc, Mutex};
+    use std::sync::Arc;
 
     #[cfg(feature = "async")]
     use std::thread;
@@ -205,7 +830,7 @@ mod tests {
     }
 
     #[test]
-    #[allow(clippy::vtable_address_comparisons)]
+    #[allow(ambiguous_wide_pointer_comparisons)]
     fn get_should_return_same_instance_for_singleton_service() {
         // arrange
         let services = ServiceCollection::new()
@@ -231,7 +856,7 @@ mod tests {
     }
 
     #[test]
-    #[allow(clippy::vtable_address_comparisons)]
+    #[allow(ambiguous_wide_pointer_comparisons)]
     fn get_should_return_different_instances_for_transient_service() {
         // arrange
         let services = ServiceCollection::new()
@@ -276,205 +901,1224 @@ mod tests {
     }
 
     #[test]
-    #[allow(clippy::vtable_address_comparisons)]
-    fn two_scoped_service_providers_should_create_different_instances() {
+    fn get_all_should_return_services_by_order() {
         // arrange
-        let services = ServiceCollection::new()
+        let mut collection = ServiceCollection::new();
+
+        collection
             .add(
-                scoped::<dyn TestService, TestServiceImpl>()
-                    .from(|_| ServiceRef::new(TestServiceImpl::default())),
+                singleton::<dyn TestService, TestServiceImpl>()
+                    .order(10)
+                    .from(|_| ServiceRef::new(TestServiceImpl { value: 1 })),
             )
-            .build_provider()
-            .unwrap();
-        let scope1 = services.create_scope();
-        let scope2 = services.create_scope();
+            .add(
+                singleton::<dyn TestService, TestService2Impl>()
+                    .from(|_| ServiceRef::new(TestService2Impl { value: 2 })),
+            );
+
+        let provider = collection.build_provider().unwrap();
 
         // act
-        let svc1 = scope1.get_required::<dyn TestService>();
-        let svc2 = scope2.get_required::<dyn TestService>();
+        let services = provider.get_all::<dyn TestService>();
+        let values: Vec<_> = services.map(|s| s.value()).collect();
 
         // assert
-        assert!(!ServiceRef::ptr_eq(&svc1, &svc2));
+        assert_eq!(&values, &[2, 1]);
+        assert_eq!(provider.get_required::<dyn TestService>().value(), 2);
     }
 
     #[test]
-    #[allow(clippy::vtable_address_comparisons)]
-    fn parent_child_scoped_service_providers_should_create_different_instances() {
+    fn get_should_return_last_registered_service_when_ordered() {
         // arrange
-        let services = ServiceCollection::new()
+        let mut collection = ServiceCollection::new();
+
+        collection
             .add(
-                scoped::<dyn TestService, TestServiceImpl>()
-                    .from(|_| ServiceRef::new(TestServiceImpl::default())),
+                singleton::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl { value: 1 })),
             )
-            .build_provider()
-            .unwrap();
-        let scope1 = services.create_scope();
-        let scope2 = scope1.create_scope();
+            .add(
+                singleton::<dyn TestService, TestService2Impl>()
+                    .order(-10)
+                    .from(|_| ServiceRef::new(TestService2Impl { value: 2 })),
+            );
+
+        let provider = collection.build_provider().unwrap();
+        let handle = provider.handle::<dyn TestService>().unwrap();
 
         // act
-        let svc1 = scope1.get_required::<dyn TestService>();
-        let svc2 = scope2.get_required::<dyn TestService>();
+        let service = provider.get::<dyn TestService>().unwrap();
 
         // assert
-        assert!(!ServiceRef::ptr_eq(&svc1, &svc2));
+        assert_eq!(service.value(), 2);
+        assert_eq!(handle.get_required(&provider).value(), 2);
+        assert_eq!(
+            provider
+                .descriptor::<dyn TestService>()
+                .unwrap()
+                .implementation_type(),
+            Type::of::<TestService2Impl>()
+        );
+        assert_eq!(
+            provider
+                .get_all::<dyn TestService>()
+                .map(|s| s.value())
+                .collect::<Vec<_>>(),
+            [2, 1]
+        );
     }
 
     #[test]
-    #[allow(clippy::vtable_address_comparisons)]
-    fn scoped_service_provider_should_have_same_singleton_when_eager_created_in_parent() {
+    fn get_all_should_return_services_by_relative_constraints() {
         // arrange
-        let services = ServiceCollection::new()
+        let mut collection = ServiceCollection::new();
+
+        collection
+            .add(
+                singleton::<dyn TestService, TestServiceImpl>()
+                    .order(-10)
+                    .after::<TestService2Impl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl { value: 1 })),
+            )
+            .add(
+                singleton::<dyn TestService, TestService2Impl>()
+                    .from(|_| ServiceRef::new(TestService2Impl { value: 2 })),
+            );
+
+        let provider = collection.build_provider().unwrap();
+
+        // act
+        let services = provider.get_all::<dyn TestService>();
+        let values: Vec<_> = services.map(|s| s.value()).collect();
+
+        // assert
+        assert_eq!(&values, &[2, 1]);
+    }
+
+    #[test]
+    fn get_all_tagged_should_return_services_with_tag() {
+        // arrange
+        let mut collection = ServiceCollection::new();
+
+        collection
+            .add(
+                singleton::<dyn TestService, TestServiceImpl>()
+                    .with_tag("http")
+                    .from(|_| ServiceRef::new(TestServiceImpl { value: 1 })),
+            )
+            .add(
+                singleton::<dyn TestService, TestService2Impl>()
+                    .from(|_| ServiceRef::new(TestService2Impl { value: 2 })),
+            );
+
+        let provider = collection.build_provider().unwrap();
+
+        // act
+        let values: Vec<_> = provider
+            .get_all_tagged::<dyn TestService>("http")
+            .map(|s| s.value())
+            .collect();
+
+        // assert
+        assert_eq!(&values, &[1]);
+    }
+
+    #[test]
+    fn get_all_with_metadata_should_return_services_paired_with_metadata() {
+        // arrange
+        struct Route(&'static str);
+        let mut collection = ServiceCollection::new();
+
+        collection
+            .add(
+                singleton::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl { value: 1 })),
+            )
+            .add(
+                singleton::<dyn TestService, TestService2Impl>()
+                    .with_metadata(Route("/orders"))
+                    .from(|_| ServiceRef::new(TestService2Impl { value: 2 })),
+            );
+
+        let provider = collection.build_provider().unwrap();
+
+        // act
+        let routes: Vec<_> = provider
+            .get_all_with_metadata::<dyn TestService, Route>()
+            .map(|(s, route)| (s.value(), route.0))
+            .collect();
+
+        // assert
+        assert_eq!(&routes, &[(2, "/orders")]);
+    }
+
+    #[test]
+    #[allow(ambiguous_wide_pointer_comparisons)]
+    fn child_should_share_singletons_of_parent() {
+        // arrange
+        let parent = ServiceCollection::new()
             .add(
                 singleton::<dyn TestService, TestServiceImpl>()
                     .from(|_| ServiceRef::new(TestServiceImpl::default())),
             )
             .build_provider()
             .unwrap();
-        let svc1 = services.get_required::<dyn TestService>();
-        let scope1 = services.create_scope();
-        let scope2 = scope1.create_scope();
+        let child = parent
+            .create_child(|services| {
+                services.add(
+                    transient::<dyn OtherTestService, OtherTestServiceImpl>()
+                        .depends_on(exactly_one::<dyn TestService>())
+                        .from(|sp| {
+                            ServiceRef::new(OtherTestServiceImpl::new(
+                                sp.get_required::<dyn TestService>(),
+                            ))
+                        }),
+                );
+            })
+            .unwrap();
 
         // act
-        let svc2 = scope1.get_required::<dyn TestService>();
-        let svc3 = scope2.get_required::<dyn TestService>();
+        let service1 = child.get_required::<dyn TestService>();
+        let service2 = parent.get_required::<dyn TestService>();
 
         // assert
-        assert!(ServiceRef::ptr_eq(&svc1, &svc2));
-        assert!(ServiceRef::ptr_eq(&svc1, &svc3));
+        assert!(child.get::<dyn OtherTestService>().is_some());
+        assert!(parent.get::<dyn OtherTestService>().is_none());
+        assert!(ServiceRef::ptr_eq(&service1, &service2));
     }
 
     #[test]
-    #[allow(clippy::vtable_address_comparisons)]
-    fn scoped_service_provider_should_have_same_singleton_when_lazy_created_in_parent() {
+    fn child_should_replace_registrations_of_parent() {
         // arrange
-        let services = ServiceCollection::new()
+        let parent = ServiceCollection::new()
             .add(
                 singleton::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl { value: 1 })),
+            )
+            .add(
+                singleton::<dyn TestService, TestService2Impl>()
+                    .from(|_| ServiceRef::new(TestService2Impl { value: 2 })),
+            )
+            .build_provider()
+            .unwrap();
+        let child = parent
+            .create_child(|services| {
+                services.add(
+                    singleton::<dyn TestService, TestServiceImpl>()
+                        .from(|_| ServiceRef::new(TestServiceImpl { value: 3 })),
+                );
+            })
+            .unwrap();
+
+        // act
+        let values: Vec<_> = child
+            .get_all::<dyn TestService>()
+            .map(|s| s.value())
+            .collect();
+
+        // assert
+        assert_eq!(&values, &[3]);
+        assert_eq!(parent.get_required::<dyn TestService>().value(), 2);
+    }
+
+    #[test]
+    #[allow(ambiguous_wide_pointer_comparisons)]
+    fn child_scope_should_resolve_scoped_services_of_parent_per_scope() {
+        // arrange
+        let parent = ServiceCollection::new()
+            .add(
+                scoped::<dyn TestService, TestServiceImpl>()
                     .from(|_| ServiceRef::new(TestServiceImpl::default())),
             )
             .build_provider()
             .unwrap();
-        let scope1 = services.create_scope();
-        let scope2 = scope1.create_scope();
-        let svc1 = services.get_required::<dyn TestService>();
+        let child = parent.create_child(|_| {}).unwrap();
+        let scope1 = child.create_scope();
+        let scope2 = child.create_scope();
 
         // act
-        let svc2 = scope1.get_required::<dyn TestService>();
-        let svc3 = scope2.get_required::<dyn TestService>();
+        let service1 = scope1.get_required::<dyn TestService>();
+        let service2 = scope2.get_required::<dyn TestService>();
 
         // assert
-        assert!(ServiceRef::ptr_eq(&svc1, &svc2));
-        assert!(ServiceRef::ptr_eq(&svc1, &svc3));
+        assert!(ServiceRef::ptr_eq(
+            &service1,
+            &scope1.get_required::<dyn TestService>()
+        ));
+        assert!(!ServiceRef::ptr_eq(&service1, &service2));
     }
 
     #[test]
-    fn service_provider_should_drop_existing_as_service() {
+    fn create_child_should_validate_combined_registrations() {
         // arrange
-        let file = new_temp_file("drop2");
+        let parent = ServiceCollection::new()
+            .add(
+                scoped::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl::default())),
+            )
+            .build_provider()
+            .unwrap();
 
         // act
-        {
-            let mut services = ServiceCollection::new();
-            services.add(existing_as_self(Droppable::new(file.clone())));
-            let _ = services.build_provider().unwrap();
-        }
+        let result = parent.create_child(|services| {
+            services.add(
+                singleton::<dyn OtherTestService, OtherTestServiceImpl>()
+                    .depends_on(exactly_one::<dyn TestService>())
+                    .from(|sp| {
+                        ServiceRef::new(OtherTestServiceImpl::new(
+                            sp.get_required::<dyn TestService>(),
+                        ))
+                    }),
+            );
+        });
 
         // assert
-        let dropped = !file.exists();
-        remove_file(&file).ok();
-        assert!(dropped);
+        assert!(result.is_err());
     }
 
     #[test]
-    fn service_provider_should_drop_lazy_initialized_service() {
+    fn create_child_should_validate_dependencies_of_inherited_registrations() {
         // arrange
-        let file = new_temp_file("drop3");
+        let parent = ServiceCollection::new()
+            .add(
+                scoped::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl::default())),
+            )
+            .add(
+                transient::<dyn OtherTestService, OtherTestServiceImpl>()
+                    .depends_on(exactly_one::<dyn TestService>())
+                    .from(|sp| {
+                        ServiceRef::new(OtherTestServiceImpl::new(
+                            sp.get_required::<dyn TestService>(),
+                        ))
+                    }),
+            )
+            .build_provider()
+            .unwrap();
 
         // act
-        {
-            let provider = ServiceCollection::new()
-                .add(existing::<Path, PathBuf>(file.clone().into_boxed_path()))
-                .add(singleton_as_self().from(|sp| {
-                    ServiceRef::new(Droppable::new(sp.get_required::<Path>().to_path_buf()))
-                }))
-                .build_provider()
-                .unwrap();
-            let _ = provider.get_required::<Droppable>();
-        }
+        let result = parent.create_child(|services| {
+            services.add(
+                singleton::<dyn AnotherTestService, AnotherTestServiceImpl>()
+                    .depends_on(exactly_one::<dyn OtherTestService>())
+                    .from(|sp| {
+                        ServiceRef::new(AnotherTestServiceImpl::new(
+                            sp.get_required::<dyn OtherTestService>(),
+                        ))
+                    }),
+            );
+        });
 
         // assert
-        let dropped = !file.exists();
-        remove_file(&file).ok();
-        assert!(dropped);
+        assert!(result
+            .err()
+            .unwrap()
+            .to_string()
+            .contains("has a singleton lifetime, but its transitive dependency"));
     }
 
     #[test]
-    fn service_provider_should_not_drop_service_if_never_instantiated() {
+    #[allow(ambiguous_wide_pointer_comparisons)]
+    fn tenant_providers_should_share_instances_for_the_same_tenant() {
         // arrange
-        let file = new_temp_file("drop5");
+        let provider = ServiceCollection::new()
+            .add(
+                tenant::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl::default())),
+            )
+            .build_provider()
+            .unwrap();
+        let tenant1 = provider.tenant("contoso");
+        let tenant2 = provider.tenant("fabrikam");
 
         // act
-        {
-            let _ = ServiceCollection::new()
-                .add(existing::<Path, PathBuf>(file.clone().into_boxed_path()))
-                .add(singleton_as_self().from(|sp| {
-                    ServiceRef::new(Droppable::new(sp.get_required::<Path>().to_path_buf()))
-                }))
-                .build_provider()
-                .unwrap();
-        }
+        let service1 = tenant1.get_required::<dyn TestService>();
+        let service2 = tenant1.create_scope().get_required::<dyn TestService>();
+        let service3 = provider.tenant("contoso").get_required::<dyn TestService>();
+        let service4 = tenant2.get_required::<dyn TestService>();
 
         // assert
-        let not_dropped = file.exists();
-        remove_file(&file).ok();
-        assert!(not_dropped);
+        assert_eq!(tenant1.key(), "contoso");
+        assert!(ServiceRef::ptr_eq(&service1, &service2));
+        assert!(ServiceRef::ptr_eq(&service1, &service3));
+        assert!(!ServiceRef::ptr_eq(&service1, &service4));
     }
 
-    #[cfg(feature = "async")]
-    #[derive(Clone)]
-    struct Holder<T: Send + Sync + Clone>(T);
+    #[test]
+    #[allow(ambiguous_wide_pointer_comparisons)]
+    fn remove_tenant_should_create_new_instances_for_the_tenant() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add(
+                tenant::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl::default())),
+            )
+            .build_provider()
+            .unwrap();
+        let service1 = provider.tenant("contoso").get_required::<dyn TestService>();
 
-    #[cfg(feature = "async")]
-    fn inject<V: Send + Sync + Clone + 'static>(value: V) -> Holder<V> {
-        Holder(value)
+        // act
+        let removed = provider.remove_tenant("contoso");
+
+        // assert
+        let service2 = provider.tenant("contoso").get_required::<dyn TestService>();
+        assert!(removed);
+        assert!(!ServiceRef::ptr_eq(&service1, &service2));
     }
 
     #[test]
-    #[cfg(feature = "async")]
-    fn service_provider_should_be_async_safe() {
+    fn get_should_not_resolve_tenant_service_outside_of_tenant() {
         // arrange
         let provider = ServiceCollection::new()
             .add(
-                singleton::<dyn TestService, TestAsyncServiceImpl>().from(|_| {
-                    ServiceRef::new(TestAsyncServiceImpl::default())
-                }),
+                tenant::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl::default())),
             )
             .build_provider()
             .unwrap();
-        let holder = inject(provider);
-        let h1 = holder.clone();
-        let h2 = holder.clone();
-        let value = Arc::new(Mutex::new(0));
-        let v1 = value.clone();
-        let v2 = value.clone();
+        let scope = provider.create_scope();
 
         // act
-        let t1 = thread::spawn(move || {
-            let service = h1.0.get_required::<dyn TestService>();
-            let mut result = v1.lock().unwrap();
-            *result += service.value();
-        });
+        let service = scope.get::<dyn TestService>();
 
-        let t2 = thread::spawn(move || {
-            let service = h2.0.get_required::<dyn TestService>();
-            let mut result = v2.lock().unwrap();
-            *result += service.value();
-        });
-        
-        t1.join().ok();
-        t2.join().ok();
+        // assert
+        assert!(service.is_none());
+        assert!(provider
+            .tenant("contoso")
+            .get::<dyn TestService>()
+            .is_some());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Service type 'dyn di::test::TestService' has a tenant lifetime and must be resolved from a tenant provider."
+    )]
+    fn get_required_should_panic_for_tenant_service_outside_of_tenant() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add(
+                tenant::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl::default())),
+            )
+            .build_provider()
+            .unwrap();
+
+        // act
+        let _ = provider.get_required::<dyn TestService>();
 
         // assert
-        assert_eq!(*value.lock().unwrap(), 3);
+        // panics
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Service type 'dyn di::test::TestService' has a tenant lifetime and must be resolved from a tenant provider."
+    )]
+    fn handle_should_panic_for_tenant_service_outside_of_tenant() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add(
+                tenant::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl::default())),
+            )
+            .build_provider()
+            .unwrap();
+        let handle = provider.handle::<dyn TestService>().unwrap();
+
+        // act
+        let _ = handle.get_required(&provider);
+
+        // assert
+        // panics
+    }
+
+    #[test]
+    #[allow(ambiguous_wide_pointer_comparisons)]
+    fn two_scoped_service_providers_should_create_different_instances() {
+        // arrange
+        let services = ServiceCollection::new()
+            .add(
+                scoped::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl::default())),
+            )
+            .build_provider()
+            .unwrap();
+        let scope1 = services.create_scope();
+        let scope2 = services.create_scope();
+
+        // act
+        let svc1 = scope1.get_required::<dyn TestService>();
+        let svc2 = scope2.get_required::<dyn TestService>();
+
+        // assert
+        assert!(!ServiceRef::ptr_eq(&svc1, &svc2));
+    }
+
+    #[test]
+    #[allow(ambiguous_wide_pointer_comparisons)]
+    fn parent_child_scoped_service_providers_should_create_different_instances() {
+        // arrange
+        let services = ServiceCollection::new()
+            .add(
+                scoped::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl::default())),
+            )
+            .build_provider()
+            .unwrap();
+        let scope1 = services.create_scope();
+        let scope2 = scope1.create_scope();
+
+        // act
+        let svc1 = scope1.get_required::<dyn TestService>();
+        let svc2 = scope2.get_required::<dyn TestService>();
+
+        // assert
+        assert!(!ServiceRef::ptr_eq(&svc1, &svc2));
+    }
+
+    #[test]
+    #[allow(ambiguous_wide_pointer_comparisons)]
+    fn scoped_service_provider_should_have_same_singleton_when_eager_created_in_parent() {
+        // arrange
+        let services = ServiceCollection::new()
+            .add(
+                singleton::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl::default())),
+            )
+            .build_provider()
+            .unwrap();
+        let svc1 = services.get_required::<dyn TestService>();
+        let scope1 = services.create_scope();
+        let scope2 = scope1.create_scope();
+
+        // act
+        let svc2 = scope1.get_required::<dyn TestService>();
+        let svc3 = scope2.get_required::<dyn TestService>();
+
+        // assert
+        assert!(ServiceRef::ptr_eq(&svc1, &svc2));
+        assert!(ServiceRef::ptr_eq(&svc1, &svc3));
+    }
+
+    #[test]
+    #[allow(ambiguous_wide_pointer_comparisons)]
+    fn scoped_service_provider_should_have_same_singleton_when_lazy_created_in_parent() {
+        // arrange
+        let services = ServiceCollection::new()
+            .add(
+                singleton::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl::default())),
+            )
+            .build_provider()
+            .unwrap();
+        let scope1 = services.create_scope();
+        let scope2 = scope1.create_scope();
+        let svc1 = services.get_required::<dyn TestService>();
+
+        // act
+        let svc2 = scope1.get_required::<dyn TestService>();
+        let svc3 = scope2.get_required::<dyn TestService>();
+
+        // assert
+        assert!(ServiceRef::ptr_eq(&svc1, &svc2));
+        assert!(ServiceRef::ptr_eq(&svc1, &svc3));
+    }
+
+    #[test]
+    fn service_provider_should_drop_existing_as_service() {
+        // arrange
+        let file = new_temp_file("drop2");
+
+        // act
+        {
+            let mut services = ServiceCollection::new();
+            services.add(existing_as_self(Droppable::new(file.clone())));
+            let _ = services.build_provider().unwrap();
+        }
+
+        // assert
+        let dropped = !file.exists();
+        remove_file(&file).ok();
+        assert!(dropped);
+    }
+
+    #[test]
+    fn service_provider_should_drop_lazy_initialized_service() {
+        // arrange
+        let file = new_temp_file("drop3");
+
+        // act
+        {
+            let provider = ServiceCollection::new()
+                .add(existing::<Path, PathBuf>(file.clone().into_boxed_path()))
+                .add(singleton_as_self().from(|sp| {
+                    ServiceRef::new(Droppable::new(sp.get_required::<Path>().to_path_buf()))
+                }))
+                .build_provider()
+                .unwrap();
+            let _ = provider.get_required::<Droppable>();
+        }
+
+        // assert
+        let dropped = !file.exists();
+        remove_file(&file).ok();
+        assert!(dropped);
+    }
+
+    #[test]
+    fn service_provider_should_not_drop_service_if_never_instantiated() {
+        // arrange
+        let file = new_temp_file("drop5");
+
+        // act
+        {
+            let _ = ServiceCollection::new()
+                .add(existing::<Path, PathBuf>(file.clone().into_boxed_path()))
+                .add(singleton_as_self().from(|sp| {
+                    ServiceRef::new(Droppable::new(sp.get_required::<Path>().to_path_buf()))
+                }))
+                .build_provider()
+                .unwrap();
+        }
+
+        // assert
+        let not_dropped = file.exists();
+        remove_file(&file).ok();
+        assert!(not_dropped);
+    }
+
+    #[cfg(feature = "async")]
+    #[derive(Clone)]
+    struct Holder<T: Send + Sync + Clone>(T);
+
+    #[cfg(feature = "async")]
+    fn inject<V: Send + Sync + Clone + 'static>(value: V) -> Holder<V> {
+        Holder(value)
+    }
+
+    #[test]
+    #[cfg(feature = "async")]
+    fn service_provider_should_be_async_safe() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add(
+                singleton::<dyn TestService, TestAsyncServiceImpl>().from(|_| {
+                    ServiceRef::new(TestAsyncServiceImpl::default())
+                }),
+            )
+            .build_provider()
+            .unwrap();
+        let holder = inject(provider);
+        let h1 = holder.clone();
+        let h2 = holder.clone();
+        let value = Arc::new(Mutex::new(0));
+        let v1 = value.clone();
+        let v2 = value.clone();
+
+        // act
+        let t1 = thread::spawn(move || {
+            let service = h1.0.get_required::<dyn TestService>();
+            let mut result = v1.lock().unwrap();
+            *result += service.value();
+        });
+
+        let t2 = thread::spawn(move || {
+            let service = h2.0.get_required::<dyn TestService>();
+            let mut result = v2.lock().unwrap();
+            *result += service.value();
+        });
+        
+        t1.join().ok();
+        t2.join().ok();
+
+        // assert
+        assert_eq!(*value.lock().unwrap(), 3);
+    }
+
+    #[derive(Clone, Default)]
+    struct TestObserver {
+        events: ServiceRef<Mutex<Vec<String>>>,
+    }
+
+    impl TestObserver {
+        fn record(&self, event: &str, descriptor: &ServiceDescriptor) {
+            let name = descriptor.implementation_type().name();
+            let name = &name[name.rfind(':').map(|i| i + 1).unwrap_or(0)..];
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("{}:{}", event, name));
+        }
+
+        fn events(&self) -> Vec<String> {
+            self.events.lock().unwrap().clone()
+        }
+    }
+
+    impl ResolutionObserver for TestObserver {
+        fn on_resolving(&self, descriptor: &ServiceDescriptor, _scope: usize) {
+            self.record("resolving", descriptor);
+        }
+
+        fn on_resolved(&self, descriptor: &ServiceDescriptor, _scope: usize) {
+            self.record("resolved", descriptor);
+        }
+
+        fn on_cache_hit(&self, descriptor: &ServiceDescriptor, _scope: usize) {
+            self.record("cache_hit", descriptor);
+        }
+
+        fn on_created(&self, descriptor: &ServiceDescriptor, _scope: usize, _elapsed: Duration) {
+            self.record("created", descriptor);
+        }
+
+        fn on_scope_created(&self, _scope: usize, _parent: usize) {
+            self.events.lock().unwrap().push("scope_created".into());
+        }
+
+        fn on_scope_dropped(&self, _scope: usize) {
+            self.events.lock().unwrap().push("scope_dropped".into());
+        }
+
+        fn on_failed(&self, service_type: &Type, _scope: usize) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("failed:{}", service_type.name()));
+        }
+    }
+
+    #[test]
+    fn observer_should_receive_resolution_events() {
+        // arrange
+        let observer = TestObserver::default();
+        let provider = ServiceCollection::new()
+            .add(
+                singleton::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl::default())),
+            )
+            .add_observer(observer.clone())
+            .build_provider()
+            .unwrap();
+
+        // act
+        let _ = provider.get_required::<dyn TestService>();
+        let _ = provider.get_required::<dyn TestService>();
+
+        // assert
+        assert_eq!(
+            observer.events(),
+            [
+                "resolving:TestServiceImpl",
+                "created:TestServiceImpl",
+                "resolved:TestServiceImpl",
+                "resolving:TestServiceImpl",
+                "cache_hit:TestServiceImpl",
+                "resolved:TestServiceImpl",
+            ]
+        );
+    }
+
+    #[test]
+    fn observer_should_receive_scope_events() {
+        // arrange
+        let observer = TestObserver::default();
+        let provider = ServiceCollection::new()
+            .add_observer(observer.clone())
+            .build_provider()
+            .unwrap();
+
+        // act
+        {
+            let _scope = provider.create_scope();
+        }
+
+        // assert
+        assert_eq!(observer.events(), ["scope_created", "scope_dropped"]);
+    }
+
+    #[test]
+    fn observer_should_receive_failure_event() {
+        // arrange
+        let observer = TestObserver::default();
+        let provider = ServiceCollection::new()
+            .add_observer(observer.clone())
+            .build_provider()
+            .unwrap();
+
+        // act
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            provider.get_required::<dyn TestService>()
+        }));
+
+        // assert
+        assert!(result.is_err());
+        assert_eq!(observer.events(), ["failed:dyn di::test::TestService"]);
+    }
+
+    struct DenyInterceptor;
+
+    impl Interceptor for DenyInterceptor {
+        fn intercept(
+            &self,
+            descriptor: &ServiceDescriptor,
+            _services: &ServiceProvider,
+        ) -> Interception {
+            if descriptor.implementation_type() == Type::of::<TestService2Impl>() {
+                Interception::Deny
+            } else {
+                Interception::Proceed
+            }
+        }
+    }
+
+    #[test]
+    fn observer_should_receive_failure_event_for_optional_service() {
+        // arrange
+        let observer = TestObserver::default();
+        let provider = ServiceCollection::new()
+            .add_observer(observer.clone())
+            .build_provider()
+            .unwrap();
+
+        // act
+        let service = provider.get::<dyn TestService>();
+
+        // assert
+        assert!(service.is_none());
+        assert_eq!(observer.events(), ["failed:dyn di::test::TestService"]);
+    }
+
+    #[test]
+    fn observer_should_receive_failure_event_when_resolution_is_denied() {
+        // arrange
+        let observer = TestObserver::default();
+        let provider = ServiceCollection::new()
+            .add(
+                transient::<dyn TestService, TestService2Impl>()
+                    .from(|_| ServiceRef::new(TestService2Impl::default())),
+            )
+            .add_observer(observer.clone())
+            .add_interceptor(DenyInterceptor)
+            .build_provider()
+            .unwrap();
+
+        // act
+        let service = provider.get_by_type(&Type::of::<dyn TestService>());
+
+        // assert
+        assert!(service.is_none());
+        assert_eq!(observer.events(), ["failed:dyn di::test::TestService"]);
+    }
+
+    #[test]
+    fn child_should_apply_interceptors_of_parent() {
+        // arrange
+        let parent = ServiceCollection::new()
+            .add_interceptor(DenyInterceptor)
+            .build_provider()
+            .unwrap();
+        let child = parent
+            .create_child(|services| {
+                services.add(
+                    transient::<dyn TestService, TestService2Impl>()
+                        .from(|_| ServiceRef::new(TestService2Impl::default())),
+                );
+            })
+            .unwrap();
+
+        // act
+        let service = child.get::<dyn TestService>();
+
+        // assert
+        assert!(service.is_none());
+    }
+
+    #[test]
+    fn child_should_notify_observers_of_parent_once() {
+        // arrange
+        let observer = TestObserver::default();
+        let parent = ServiceCollection::new()
+            .add(
+                singleton::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl::default())),
+            )
+            .add_observer(observer.clone())
+            .build_provider()
+            .unwrap();
+        let child = parent
+            .create_child(|services| {
+                services.add(
+                    transient::<dyn OtherTestService, OtherTestServiceImpl>().from(|sp| {
+                        ServiceRef::new(OtherTestServiceImpl::new(
+                            sp.get_required::<dyn TestService>(),
+                        ))
+                    }),
+                );
+            })
+            .unwrap();
+
+        // act
+        let _ = child.get_required::<dyn OtherTestService>();
+
+        // assert
+        assert_eq!(
+            observer.events(),
+            [
+                "resolving:OtherTestServiceImpl",
+                "resolving:TestServiceImpl",
+                "created:TestServiceImpl",
+                "resolved:TestServiceImpl",
+                "created:OtherTestServiceImpl",
+                "resolved:OtherTestServiceImpl",
+            ]
+        );
+    }
+
+    struct ReplaceInterceptor;
+
+    impl Interceptor for ReplaceInterceptor {
+        fn intercept(
+            &self,
+            _descriptor: &ServiceDescriptor,
+            _services: &ServiceProvider,
+        ) -> Interception {
+            let double: ServiceRef<dyn TestService> =
+                ServiceRef::new(TestServiceImpl { value: 42 });
+            Interception::Replace(ServiceRef::new(double))
+        }
+    }
+
+    struct Doubled(ServiceRef<dyn TestService>);
+
+    impl TestService for Doubled {
+        fn value(&self) -> usize {
+            self.0.value() * 2
+        }
+    }
+
+    struct DoubleInterceptor;
+
+    impl Interceptor for DoubleInterceptor {
+        fn created(
+            &self,
+            _descriptor: &ServiceDescriptor,
+            _services: &ServiceProvider,
+            instance: ServiceRef<dyn Any>,
+        ) -> ServiceRef<dyn Any> {
+            let inner = instance
+                .downcast_ref::<ServiceRef<dyn TestService>>()
+                .unwrap()
+                .clone();
+            let outer: ServiceRef<dyn TestService> = ServiceRef::new(Doubled(inner));
+            ServiceRef::new(outer)
+        }
+    }
+
+    #[test]
+    fn interceptor_should_deny_resolution() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add(
+                transient::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl { value: 1 })),
+            )
+            .add(
+                transient::<dyn TestService, TestService2Impl>()
+                    .from(|_| ServiceRef::new(TestService2Impl { value: 2 })),
+            )
+            .add_interceptor(DenyInterceptor)
+            .build_provider()
+            .unwrap();
+
+        // act
+        let service = provider.get::<dyn TestService>();
+        let values: Vec<_> = provider
+            .get_all::<dyn TestService>()
+            .map(|s| s.value())
+            .collect();
+
+        // assert
+        assert!(service.is_none());
+        assert_eq!(&values, &[1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Resolution of service type 'dyn di::test::TestService' was denied.")]
+    fn get_required_should_panic_when_resolution_is_denied() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add(
+                transient::<dyn TestService, TestService2Impl>()
+                    .from(|_| ServiceRef::new(TestService2Impl::default())),
+            )
+            .add_interceptor(DenyInterceptor)
+            .build_provider()
+            .unwrap();
+
+        // act
+        let _ = provider.get_required::<dyn TestService>();
+
+        // assert
+        // panics
+    }
+
+    #[test]
+    fn interceptor_should_replace_service() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add(
+                transient::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl::default())),
+            )
+            .add_interceptor(ReplaceInterceptor)
+            .build_provider()
+            .unwrap();
+
+        // act
+        let service = provider.get_required::<dyn TestService>();
+
+        // assert
+        assert_eq!(service.value(), 42);
+    }
+
+    #[test]
+    #[allow(ambiguous_wide_pointer_comparisons)]
+    fn interceptor_should_post_process_created_service_before_it_is_cached() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add(
+                singleton::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl { value: 21 })),
+            )
+            .add_interceptor(DoubleInterceptor)
+            .build_provider()
+            .unwrap();
+
+        // act
+        let service1 = provider.get_required::<dyn TestService>();
+        let service2 = provider.get_required::<dyn TestService>();
+
+        // assert
+        assert_eq!(service1.value(), 42);
+        assert!(ServiceRef::ptr_eq(&service1, &service2));
+    }
+
+    #[test]
+    fn get_by_type_should_return_registered_service() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add(
+                singleton::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl { value: 42 })),
+            )
+            .build_provider()
+            .unwrap();
+
+        // act
+        let service = provider
+            .get_by_type(&Type::of::<dyn TestService>())
+            .unwrap();
+
+        // assert
+        let service = service
+            .downcast_ref::<ServiceRef<dyn TestService>>()
+            .unwrap();
+        assert_eq!(service.value(), 42);
+    }
+
+    #[test]
+    fn get_by_type_should_return_none_when_service_is_unregistered() {
+        // arrange
+        let provider = ServiceCollection::new().build_provider().unwrap();
+
+        // act
+        let service = provider.get_by_type(&Type::of::<dyn TestService>());
+
+        // assert
+        assert!(service.is_none());
+    }
+
+    #[test]
+    fn get_all_by_type_should_return_all_services() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add(
+                singleton::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl { value: 1 })),
+            )
+            .add(
+                singleton::<dyn TestService, TestService2Impl>()
+                    .from(|_| ServiceRef::new(TestService2Impl { value: 2 })),
+            )
+            .build_provider()
+            .unwrap();
+
+        // act
+        let values: Vec<_> = provider
+            .get_all_by_type(&Type::of::<dyn TestService>())
+            .map(|s| {
+                s.downcast_ref::<ServiceRef<dyn TestService>>()
+                    .unwrap()
+                    .value()
+            })
+            .collect();
+
+        // assert
+        assert_eq!(&values, &[1, 2]);
+    }
+
+    #[test]
+    fn contains_should_return_whether_service_is_registered() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add(
+                singleton::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl::default())),
+            )
+            .build_provider()
+            .unwrap();
+
+        // act
+        let registered = provider.contains(&Type::of::<dyn TestService>());
+        let unregistered = provider.contains(&Type::of::<dyn OtherTestService>());
+
+        // assert
+        assert!(registered);
+        assert!(!unregistered);
+    }
+
+    #[test]
+    fn descriptors_should_enumerate_all_registered_services() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add(
+                singleton::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl::default())),
+            )
+            .add(
+                transient::<dyn TestService, TestService2Impl>()
+                    .from(|_| ServiceRef::new(TestService2Impl::default())),
+            )
+            .add(
+                scoped::<dyn OtherTestService, OtherTestServiceImpl>().from(|sp| {
+                    ServiceRef::new(OtherTestServiceImpl::new(
+                        sp.get_required::<dyn TestService>(),
+                    ))
+                }),
+            )
+            .build_provider()
+            .unwrap();
+
+        // act
+        let mut names: Vec<_> = provider
+            .descriptors()
+            .map(|d| d.implementation_type().name())
+            .collect();
+
+        // assert
+        names.sort_unstable();
+        assert_eq!(
+            names,
+            [
+                "di::test::OtherTestServiceImpl",
+                "di::test::TestService2Impl",
+                "di::test::TestServiceImpl",
+            ]
+        );
+    }
+
+    #[test]
+    fn is_registered_should_not_instantiate_service() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add(
+                singleton::<dyn TestService, TestServiceImpl>()
+                    .from(|_| panic!("The service should not be instantiated.")),
+            )
+            .build_provider()
+            .unwrap();
+
+        // act
+        let registered = provider.is_registered::<dyn TestService>();
+        let unregistered = provider.is_registered::<dyn OtherTestService>();
+
+        // assert
+        assert!(registered);
+        assert!(!unregistered);
+    }
+
+    #[test]
+    fn count_should_return_number_of_registered_services() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add(
+                singleton::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl::default())),
+            )
+            .add(
+                singleton::<dyn TestService, TestService2Impl>()
+                    .from(|_| ServiceRef::new(TestService2Impl::default())),
+            )
+            .build_provider()
+            .unwrap();
+
+        // act
+        let count = provider.count::<dyn TestService>();
+
+        // assert
+        assert_eq!(count, 2);
+        assert_eq!(provider.count::<dyn OtherTestService>(), 0);
+    }
+
+    #[test]
+    fn descriptor_should_return_last_registered_descriptor() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add(
+                singleton::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl::default())),
+            )
+            .add(
+                transient::<dyn TestService, TestService2Impl>()
+                    .from(|_| ServiceRef::new(TestService2Impl::default())),
+            )
+            .build_provider()
+            .unwrap();
+
+        // act
+        let descriptor = provider.descriptor::<dyn TestService>().unwrap();
+
+        // assert
+        assert_eq!(descriptor.lifetime(), ServiceLifetime::Transient);
+        assert_eq!(
+            descriptor.implementation_type(),
+            &Type::of::<TestService2Impl>()
+        );
+    }
+
+    #[test]
+    #[allow(ambiguous_wide_pointer_comparisons)]
+    fn create_scope_should_share_descriptors_and_defer_scoped_instances() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add(
+                scoped::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl::default())),
+            )
+            .build_provider()
+            .unwrap();
+
+        // act
+        let scope = provider.create_scope();
+
+        // assert
+        assert!(ServiceRef::ptr_eq(&provider.registry, &scope.registry));
+        assert!(scope.scope.instances.get().is_none());
+
+        let _ = scope.get_required::<dyn TestService>();
+
+        assert_eq!(scope.scope.instances.get().unwrap().len(), 1);
+        assert!(provider.scope.instances.get().is_none());
     }
 }
@@ -0,0 +1,48 @@
+use crate::{provider::Instance, ServiceProvider};
+use std::ops::Deref;
+
+// the instances of the services with a tenant lifetime, which are shared by
+// every provider and scope created for the same tenant
+pub(crate) struct Tenant {
+    pub(crate) key: String,
+    pub(crate) instances: Box<[Instance]>,
+}
+
+impl Tenant {
+    pub(crate) fn new(key: &str, count: usize) -> Self {
+        Self {
+            key: key.to_owned(),
+            instances: (0..count).map(|_| Instance::default()).collect(),
+        }
+    }
+}
+
+/// Represents a [service provider](struct.ServiceProvider.html) for a single tenant.
+///
+/// # Remarks
+///
+/// Services with a [tenant lifetime](enum.ServiceLifetime.html#variant.Tenant) are created once
+/// for each tenant key. Every tenant provider and scope created for the same key shares the same
+/// tenant instances, while services with any other lifetime behave as they do for the root provider.
+pub struct TenantProvider {
+    pub(crate) provider: ServiceProvider,
+}
+
+impl TenantProvider {
+    pub(crate) fn new(provider: ServiceProvider) -> Self {
+        Self { provider }
+    }
+
+    /// Gets the key of the tenant.
+    pub fn key(&self) -> &str {
+        self.provider.tenant_key().unwrap()
+    }
+}
+
+impl Deref for TenantProvider {
+    type Target = ServiceProvider;
+
+    fn deref(&self) -> &Self::Target {
+        &self.provider
+    }
+}
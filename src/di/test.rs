@@ -1,4 +1,7 @@
-use crate::ServiceRef;
+use crate::{
+    exactly_one, ServiceCollection, ServiceDescriptorBuilder, ServiceLifetime, ServiceRef,
+    Shareable, Type,
+};
 use std::env;
 use std::fs::{remove_file, File};
 use std::path::PathBuf;
@@ -14,13 +17,43 @@ pub(crate) fn new_temp_file(name: &str) -> PathBuf {
     path
 }
 
-pub(crate) trait TestService {
+pub(crate) fn new_dependent_services(
+    dependency: ServiceLifetime,
+    dependent: ServiceLifetime,
+) -> ServiceCollection {
+    let mut services = ServiceCollection::new();
+
+    services
+        .add(
+            ServiceDescriptorBuilder::<dyn TestService, TestServiceImpl>::new(
+                dependency,
+                Type::of::<TestServiceImpl>(),
+            )
+            .from(|_| ServiceRef::new(TestServiceImpl::default())),
+        )
+        .add(
+            ServiceDescriptorBuilder::<dyn OtherTestService, OtherTestServiceImpl>::new(
+                dependent,
+                Type::of::<OtherTestServiceImpl>(),
+            )
+            .depends_on(exactly_one::<dyn TestService>())
+            .from(|sp| {
+                ServiceRef::new(OtherTestServiceImpl::new(
+                    sp.get_required::<dyn TestService>(),
+                ))
+            }),
+        );
+
+    services
+}
+
+pub(crate) trait TestService: Shareable {
     fn value(&self) -> usize;
 }
 
-pub(crate) trait OtherTestService {}
+pub(crate) trait OtherTestService: Shareable {}
 
-pub(crate) trait AnotherTestService {}
+pub(crate) trait AnotherTestService: Shareable {}
 
 #[derive(Default)]
 pub(crate) struct TestServiceImpl {
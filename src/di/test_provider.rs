@@ -0,0 +1,373 @@
+use crate::{
+    singleton_factory, ResolutionObserver, ServiceCollection, ServiceDescriptor, ServiceLifetime,
+    ServiceProvider, ServiceRef, Type, ValidationError,
+};
+use std::any::Any;
+use std::ops::Deref;
+use std::sync::Mutex;
+use std::time::Duration;
+
+#[derive(Default)]
+struct Recorder {
+    resolved: Mutex<Vec<Type>>,
+    singletons: Mutex<Vec<Type>>,
+}
+
+struct RecordingObserver(ServiceRef<Recorder>);
+
+impl ResolutionObserver for RecordingObserver {
+    fn on_resolved(&self, descriptor: &ServiceDescriptor, _scope: usize) {
+        self.0
+            .resolved
+            .lock()
+            .unwrap()
+            .push(descriptor.service_type().clone());
+    }
+
+    fn on_created(&self, descriptor: &ServiceDescriptor, _scope: usize, _elapsed: Duration) {
+        if descriptor.lifetime() == ServiceLifetime::Singleton {
+            self.0
+                .singletons
+                .lock()
+                .unwrap()
+                .push(descriptor.implementation_type().clone());
+        }
+    }
+}
+
+/// Represents a builder for a [test service provider](struct.TestServiceProvider.html).
+pub struct TestServiceProviderBuilder {
+    services: ServiceCollection,
+    expected_singletons: Vec<Type>,
+}
+
+impl TestServiceProviderBuilder {
+    /// Overrides all registrations of the specified service type with an instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `instance` - The instance, such as a test double, that replaces the registered services
+    #[track_caller]
+    pub fn override_with<T: Any + ?Sized>(&mut self, instance: ServiceRef<T>) -> &mut Self {
+        self.override_with_descriptor(singleton_factory(move |_| instance.clone()))
+    }
+
+    /// Overrides all registrations of a service type with the specified service descriptor.
+    ///
+    /// # Arguments
+    ///
+    /// * `descriptor` - The [service descriptor](struct.ServiceDescriptor.html) that replaces
+    ///   all registrations of the same service type
+    ///
+    /// # Remarks
+    ///
+    /// Conditional registrations of the same service type are also replaced, whether or not
+    /// their conditions would be met.
+    pub fn override_with_descriptor(&mut self, descriptor: ServiceDescriptor) -> &mut Self {
+        let service_type = descriptor.service_type().clone();
+
+        for index in (0..self.services.len()).rev() {
+            if self.services[index].service_type() == service_type {
+                self.services.remove(index);
+            }
+        }
+
+        self.services
+            .conditional
+            .retain(|registration| registration.descriptor.service_type() != service_type);
+
+        // an override is intentional and never an unexpected singleton
+        self.expected_singletons
+            .push(descriptor.implementation_type().clone());
+        self.services.add(descriptor);
+        self
+    }
+
+    /// Declares that a singleton implementation of the specified type is expected to be created.
+    pub fn expect_singleton<T: Any + ?Sized>(&mut self) -> &mut Self {
+        self.expected_singletons.push(Type::of::<T>());
+        self
+    }
+
+    /// Builds and returns a new [test service provider](struct.TestServiceProvider.html).
+    pub fn build(&self) -> Result<TestServiceProvider, ValidationError> {
+        let recorder = ServiceRef::new(Recorder::default());
+        let observer: ServiceRef<dyn ResolutionObserver> =
+            ServiceRef::new(RecordingObserver(recorder.clone()));
+
+        Ok(TestServiceProvider {
+            provider: self.services.build_provider_with(vec![observer])?,
+            recorder,
+            expected_singletons: self.expected_singletons.clone(),
+        })
+    }
+}
+
+/// Represents a [service provider](struct.ServiceProvider.html) that records service resolution for test assertions.
+pub struct TestServiceProvider {
+    provider: ServiceProvider,
+    recorder: ServiceRef<Recorder>,
+    expected_singletons: Vec<Type>,
+}
+
+impl TestServiceProvider {
+    /// Creates and returns a new builder that starts from the specified services.
+    ///
+    /// # Arguments
+    ///
+    /// * `services` - The [service collection](struct.ServiceCollection.html), typically from production code
+    pub fn builder(services: ServiceCollection) -> TestServiceProviderBuilder {
+        TestServiceProviderBuilder {
+            services,
+            expected_singletons: Vec::new(),
+        }
+    }
+
+    /// Gets the number of times a service of the specified type was resolved.
+    pub fn resolution_count<T: Any + ?Sized>(&self) -> usize {
+        let key = Type::of::<T>();
+        let resolved = self.recorder.resolved.lock().unwrap();
+        resolved.iter().filter(|t| *t == key).count()
+    }
+
+    /// Asserts that a service of the specified type was resolved.
+    ///
+    /// # Panics
+    ///
+    /// A service of type `T` was never resolved.
+    pub fn assert_resolved<T: Any + ?Sized>(&self) {
+        if self.resolution_count::<T>() == 0 {
+            panic!("Service type '{}' was not resolved.", Type::of::<T>());
+        }
+    }
+
+    /// Asserts that a service of the specified type was never resolved.
+    ///
+    /// # Panics
+    ///
+    /// A service of type `T` was resolved.
+    pub fn assert_not_resolved<T: Any + ?Sized>(&self) {
+        let count = self.resolution_count::<T>();
+
+        if count > 0 {
+            panic!(
+                "Service type '{}' was resolved {} time(s).",
+                Type::of::<T>(),
+                count
+            );
+        }
+    }
+
+    /// Asserts that only expected singletons were created.
+    ///
+    /// # Panics
+    ///
+    /// A singleton was created whose implementation type was not declared with
+    /// [expect_singleton](struct.TestServiceProviderBuilder.html#method.expect_singleton).
+    pub fn assert_no_unexpected_singletons(&self) {
+        let singletons = self.recorder.singletons.lock().unwrap();
+        let unexpected: Vec<_> = singletons
+            .iter()
+            .filter(|t| !self.expected_singletons.contains(t))
+            .map(|t| t.name())
+            .collect();
+
+        if !unexpected.is_empty() {
+            panic!(
+                "Unexpected singletons were created: {}.",
+                unexpected.join(", ")
+            );
+        }
+    }
+}
+
+impl Deref for TestServiceProvider {
+    type Target = ServiceProvider;
+
+    fn deref(&self) -> &Self::Target {
+        &self.provider
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{test::*, testing::*, *};
+
+    #[test]
+    fn override_with_should_replace_all_registrations() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services
+            .add(
+                singleton::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl { value: 1 })),
+            )
+            .add(
+                singleton::<dyn TestService, TestService2Impl>()
+                    .from(|_| ServiceRef::new(TestService2Impl { value: 2 })),
+            );
+
+        let double: ServiceRef<dyn TestService> = ServiceRef::new(TestServiceImpl { value: 42 });
+        let provider = TestServiceProvider::builder(services)
+            .override_with(double)
+            .build()
+            .unwrap();
+
+        // act
+        let values: Vec<_> = provider
+            .get_all::<dyn TestService>()
+            .map(|s| s.value())
+            .collect();
+
+        // assert
+        assert_eq!(&values, &[42]);
+    }
+
+    #[test]
+    fn override_with_should_replace_conditional_registrations() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services.add_when(
+            || true,
+            singleton::<dyn TestService, TestServiceImpl>()
+                .from(|_| ServiceRef::new(TestServiceImpl { value: 1 })),
+        );
+
+        let double: ServiceRef<dyn TestService> = ServiceRef::new(TestServiceImpl { value: 42 });
+        let provider = TestServiceProvider::builder(services)
+            .override_with(double)
+            .build()
+            .unwrap();
+
+        // act
+        let values: Vec<_> = provider
+            .get_all::<dyn TestService>()
+            .map(|s| s.value())
+            .collect();
+
+        // assert
+        assert_eq!(&values, &[42]);
+    }
+
+    #[test]
+    fn assert_resolved_should_succeed_when_service_was_resolved() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services
+            .add(
+                singleton::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl { value: 1 })),
+            )
+            .add(
+                transient::<dyn OtherTestService, OtherTestServiceImpl>().from(|sp| {
+                    ServiceRef::new(OtherTestServiceImpl::new(
+                        sp.get_required::<dyn TestService>(),
+                    ))
+                }),
+            );
+
+        let provider = TestServiceProvider::builder(services).build().unwrap();
+
+        // act
+        let _ = provider.get_required::<dyn OtherTestService>();
+
+        // assert
+        provider.assert_resolved::<dyn OtherTestService>();
+        provider.assert_resolved::<dyn TestService>();
+        assert_eq!(provider.resolution_count::<dyn OtherTestService>(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Service type 'dyn di::test::OtherTestService' was not resolved.")]
+    fn assert_resolved_should_panic_when_service_was_not_resolved() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services
+            .add(
+                singleton::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl { value: 1 })),
+            )
+            .add(
+                transient::<dyn OtherTestService, OtherTestServiceImpl>().from(|sp| {
+                    ServiceRef::new(OtherTestServiceImpl::new(
+                        sp.get_required::<dyn TestService>(),
+                    ))
+                }),
+            );
+
+        let provider = TestServiceProvider::builder(services).build().unwrap();
+
+        // act
+        provider.assert_resolved::<dyn OtherTestService>();
+
+        // assert
+        // panics
+    }
+
+    #[test]
+    #[should_panic(expected = "Unexpected singletons were created: di::test::TestService2Impl.")]
+    fn assert_no_unexpected_singletons_should_panic_when_unexpected_singleton_was_created() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services
+            .add(
+                singleton::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl { value: 1 })),
+            )
+            .add(
+                singleton::<dyn TestService, TestService2Impl>()
+                    .from(|_| ServiceRef::new(TestService2Impl { value: 2 })),
+            );
+
+        let provider = TestServiceProvider::builder(services)
+            .expect_singleton::<TestServiceImpl>()
+            .build()
+            .unwrap();
+
+        let _ = provider.get_all::<dyn TestService>().count();
+
+        // act
+        provider.assert_no_unexpected_singletons();
+
+        // assert
+        // panics
+    }
+
+    #[test]
+    fn assert_no_unexpected_singletons_should_ignore_overrides() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services
+            .add(
+                singleton::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl { value: 1 })),
+            )
+            .add(
+                transient::<dyn OtherTestService, OtherTestServiceImpl>().from(|sp| {
+                    ServiceRef::new(OtherTestServiceImpl::new(
+                        sp.get_required::<dyn TestService>(),
+                    ))
+                }),
+            );
+
+        let double: ServiceRef<dyn TestService> = ServiceRef::new(TestServiceImpl { value: 42 });
+        let provider = TestServiceProvider::builder(services)
+            .override_with(double)
+            .build()
+            .unwrap();
+
+        let _ = provider.get_required::<dyn OtherTestService>();
+
+        // act
+        provider.assert_no_unexpected_singletons();
+
+        // assert
+        // didn't panic
+    }
+}
@@ -0,0 +1,85 @@
+#![cfg(unix)]
+
+// a termination signal is delivered to the whole process; therefore, this test
+// runs in its own test binary rather than alongside any other test
+
+use di::{hosting::*, *};
+use std::process::Command;
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+struct RecordingService {
+    name: &'static str,
+    events: ServiceRef<Mutex<Vec<String>>>,
+}
+
+impl RecordingService {
+    fn new(name: &'static str, events: ServiceRef<Mutex<Vec<String>>>) -> Self {
+        Self { name, events }
+    }
+}
+
+impl HostedService for RecordingService {
+    fn start(&self) {
+        self.events
+            .lock()
+            .unwrap()
+            .push(format!("start {}", self.name));
+    }
+
+    fn stop(&self) {
+        self.events
+            .lock()
+            .unwrap()
+            .push(format!("stop {}", self.name));
+    }
+}
+
+#[test]
+fn run_should_stop_services_when_terminated() {
+    // arrange
+    let events = ServiceRef::new(Mutex::new(Vec::new()));
+    let service_events = events.clone();
+    let mut services = ServiceCollection::new();
+
+    services.add(
+        singleton::<dyn HostedService, RecordingService>()
+            .from(move |_| ServiceRef::new(RecordingService::new("only", service_events.clone()))),
+    );
+
+    let host = Host::build(&services).unwrap();
+    let shutdown = host.shutdown_handle();
+    let (stopped, watched) = channel::<()>();
+
+    // the watchdog unblocks the host if the signal never arrives
+    let watchdog = thread::spawn(
+        move || match watched.recv_timeout(Duration::from_secs(10)) {
+            Err(RecvTimeoutError::Timeout) => {
+                shutdown.shutdown();
+                true
+            }
+            _ => false,
+        },
+    );
+
+    host.shutdown_on_signal().unwrap();
+
+    let mut kill = Command::new("kill")
+        .args(["-TERM", &std::process::id().to_string()])
+        .spawn()
+        .unwrap();
+
+    // act
+    host.run();
+
+    // assert
+    stopped.send(()).unwrap();
+    kill.wait().unwrap();
+    assert!(
+        !watchdog.join().unwrap(),
+        "The host was not shut down by the signal."
+    );
+    assert_eq!(&*events.lock().unwrap(), &["start only", "stop only"]);
+}
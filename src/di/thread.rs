@@ -0,0 +1,221 @@
+use crate::{provider::Instance, ServiceRef};
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+#[cfg(not(feature = "async"))]
+type WeakRef<T> = std::rc::Weak<T>;
+
+#[cfg(feature = "async")]
+type WeakRef<T> = std::sync::Weak<T>;
+
+type Entry = (WeakRef<Instance>, ServiceRef<dyn Any>);
+
+// the per-thread instances are keyed by the cache key of their descriptor. the entries of the
+// current thread are removed when the service provider that owns the descriptor is dropped.
+// the instance cell of the descriptor is held weakly so that the entries of other threads can
+// be removed the next time those threads create a per-thread instance.
+thread_local! {
+    static INSTANCES: RefCell<HashMap<usize, Entry>> = RefCell::new(HashMap::new());
+}
+
+pub(crate) fn get_or_create(
+    key: usize,
+    owner: &ServiceRef<Instance>,
+    create: impl FnOnce() -> ServiceRef<dyn Any>,
+) -> ServiceRef<dyn Any> {
+    let cached = INSTANCES.with(|instances| {
+        instances
+            .borrow()
+            .get(&key)
+            .map(|(_, instance)| instance.clone())
+    });
+
+    if let Some(instance) = cached {
+        return instance;
+    }
+
+    // the borrow is released while the instance is created because its
+    // factory may resolve other per-thread services
+    let instance = create();
+
+    // the removed instances are dropped after the borrow is released because
+    // dropping an instance may drop a service provider with per-thread services
+    let stale = INSTANCES.with(|instances| {
+        let mut instances = instances.borrow_mut();
+        let keys: Vec<_> = instances
+            .iter()
+            .filter(|(_, (owner, _))| owner.strong_count() == 0)
+            .map(|(key, _)| *key)
+            .collect();
+        let stale: Vec<_> = keys
+            .iter()
+            .filter_map(|key| instances.remove(key))
+            .collect();
+
+        instances.insert(key, (ServiceRef::downgrade(owner), instance.clone()));
+        stale
+    });
+
+    drop(stale);
+    instance
+}
+
+pub(crate) fn remove(keys: impl Iterator<Item = usize>) {
+    let removed: Vec<_> = INSTANCES
+        .try_with(|instances| {
+            let mut instances = instances.borrow_mut();
+            keys.filter_map(|key| instances.remove(&key)).collect()
+        })
+        .unwrap_or_default();
+
+    drop(removed);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{test::*, *};
+    use std::fs::remove_file;
+    use std::path::{Path, PathBuf};
+
+    #[cfg(feature = "async")]
+    use std::sync::mpsc::channel;
+
+    #[test]
+    fn get_should_return_same_instance_on_same_thread() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add(
+                per_thread::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl::default())),
+            )
+            .build_provider()
+            .unwrap();
+        let scope = provider.create_scope();
+
+        // act
+        let svc1 = provider.get_required::<dyn TestService>();
+        let svc2 = scope.get_required::<dyn TestService>();
+
+        // assert
+        assert!(ServiceRef::ptr_eq(&svc1, &svc2));
+    }
+
+    #[test]
+    fn get_should_return_new_instance_for_each_provider() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services.add(
+            per_thread::<dyn TestService, TestServiceImpl>()
+                .from(|_| ServiceRef::new(TestServiceImpl::default())),
+        );
+
+        let provider1 = services.build_provider().unwrap();
+        let provider2 = services.build_provider().unwrap();
+
+        // act
+        let svc1 = provider1.get_required::<dyn TestService>();
+        let svc2 = provider2.get_required::<dyn TestService>();
+
+        // assert
+        assert!(!ServiceRef::ptr_eq(&svc1, &svc2));
+    }
+
+    #[test]
+    fn per_thread_instance_should_be_dropped_with_provider() {
+        // arrange
+        let file = new_temp_file("drop_per_thread");
+
+        // act
+        {
+            let provider = ServiceCollection::new()
+                .add(existing::<Path, PathBuf>(file.clone().into_boxed_path()))
+                .add(per_thread::<Droppable, Droppable>().from(|sp| {
+                    ServiceRef::new(Droppable::new(sp.get_required::<Path>().to_path_buf()))
+                }))
+                .build_provider()
+                .unwrap();
+            let _ = provider.get_required::<Droppable>();
+        }
+
+        // assert
+        let dropped = !file.exists();
+        remove_file(&file).ok();
+        assert!(dropped);
+    }
+
+    #[test]
+    #[cfg(feature = "async")]
+    fn per_thread_instance_of_other_thread_should_be_dropped_after_provider() {
+        // arrange
+        let file = new_temp_file("drop_per_thread_other_thread");
+        let provider = ServiceCollection::new()
+            .add(existing::<Path, PathBuf>(file.clone().into_boxed_path()))
+            .add(per_thread::<Droppable, Droppable>().from(|sp| {
+                ServiceRef::new(Droppable::new(sp.get_required::<Path>().to_path_buf()))
+            }))
+            .build_provider()
+            .unwrap();
+        let other = provider.clone();
+        let (resolved, wait_resolved) = channel();
+        let (dropped, wait_dropped) = channel();
+        let path = file.clone();
+        let thread = std::thread::spawn(move || {
+            let _ = other.get_required::<Droppable>();
+            drop(other);
+            resolved.send(()).unwrap();
+            wait_dropped.recv().unwrap();
+
+            // the stale instance is dropped when another per-thread instance is created
+            let alive = path.exists();
+            let provider = ServiceCollection::new()
+                .add(
+                    per_thread::<dyn TestService, TestServiceImpl>()
+                        .from(|_| ServiceRef::new(TestServiceImpl::default())),
+                )
+                .build_provider()
+                .unwrap();
+            let _ = provider.get_required::<dyn TestService>();
+            (alive, path.exists())
+        });
+
+        wait_resolved.recv().unwrap();
+
+        // act
+        drop(provider);
+        dropped.send(()).unwrap();
+
+        // assert
+        let (alive, exists) = thread.join().unwrap();
+        remove_file(&file).ok();
+        assert!(alive);
+        assert!(!exists);
+    }
+
+    #[test]
+    #[cfg(feature = "async")]
+    fn get_should_return_new_instance_on_other_thread() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add(
+                per_thread::<dyn TestService, TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl::default())),
+            )
+            .build_provider()
+            .unwrap();
+        let other = provider.clone();
+        let svc1 = provider.get_required::<dyn TestService>();
+
+        // act
+        let svc2 = std::thread::spawn(move || {
+            let svc = other.get_required::<dyn TestService>();
+            ServiceRef::as_ptr(&svc) as *const () as usize
+        })
+        .join()
+        .unwrap();
+
+        // assert
+        assert_ne!(ServiceRef::as_ptr(&svc1) as *const () as usize, svc2);
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
-    ServiceCardinality, ServiceCollection, ServiceDependency, ServiceDescriptor, ServiceLifetime,
-    Type,
+    ordering::sort, ServiceCardinality, ServiceCollection, ServiceDependency, ServiceDescriptor,
+    ServiceLifetime, Type,
 };
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
@@ -27,6 +27,10 @@ pub struct ValidationError {
 }
 
 impl ValidationError {
+    pub(crate) fn new(messages: Vec<String>) -> Self {
+        Self::fail(messages.iter().map(ValidationResult::fail).collect())
+    }
+
     fn fail(results: Vec<ValidationResult>) -> Self {
         Self {
             message: if results.is_empty() {
@@ -116,6 +120,11 @@ impl<'a> CircularDependency<'a> {
         queue.push(dependency);
 
         while let Some(current) = queue.pop() {
+            // a lazy dependency is not resolved during activation, which breaks the cycle
+            if current.is_lazy() {
+                continue;
+            }
+
             if let Some(descriptor) = self.lookup.get(current.injected_type()) {
                 if descriptor.service_type() != root.service_type() {
                     queue.extend(descriptor.dependencies());
@@ -139,13 +148,15 @@ impl<'a> ValidationRule<'a> for CircularDependency<'a> {
     }
 }
 
-struct SingletonDependsOnScoped<'a> {
+// a service with a longer lifetime must not capture a dependency with a shorter lifetime,
+// where the length of a lifetime is its width and a width of zero never captures
+struct CaptiveDependency<'a> {
     lookup: &'a HashMap<&'a Type, &'a ServiceDescriptor>,
     visited: RefCell<HashSet<&'a Type>>,
     queue: RefCell<Vec<&'a ServiceDescriptor>>,
 }
 
-impl<'a> SingletonDependsOnScoped<'a> {
+impl<'a> CaptiveDependency<'a> {
     fn new(lookup: &'a HashMap<&'a Type, &'a ServiceDescriptor>) -> Self {
         Self {
             lookup,
@@ -155,9 +166,26 @@ impl<'a> SingletonDependsOnScoped<'a> {
     }
 }
 
-impl<'a> ValidationRule<'a> for SingletonDependsOnScoped<'a> {
+// a per-thread instance is neither longer nor shorter lived than a scoped or tenant instance,
+// so it is not compared by width. a per-thread service is captured by any service with a
+// cached lifetime and it captures any service with a cached lifetime other than a singleton.
+fn is_captive(lifetime: ServiceLifetime, dependency: ServiceLifetime) -> bool {
+    let width = dependency.width();
+
+    if dependency == ServiceLifetime::PerThread {
+        lifetime.width() > 0
+    } else if lifetime == ServiceLifetime::PerThread {
+        width > 0 && width < ServiceLifetime::Singleton.width()
+    } else {
+        width > 0 && width < lifetime.width()
+    }
+}
+
+impl<'a> ValidationRule<'a> for CaptiveDependency<'a> {
     fn evaluate(&self, descriptor: &'a ServiceDescriptor, results: &mut Vec<ValidationResult>) {
-        if descriptor.lifetime() != ServiceLifetime::Singleton {
+        let lifetime = descriptor.lifetime();
+
+        if lifetime.width() == 0 && lifetime != ServiceLifetime::PerThread {
             return;
         }
 
@@ -178,13 +206,15 @@ impl<'a> ValidationRule<'a> for SingletonDependsOnScoped<'a> {
                 if let Some(next) = self.lookup.get(dependency.injected_type()) {
                     queue.push(next);
 
-                    if next.lifetime() == ServiceLifetime::Scoped {
+                    if is_captive(lifetime, next.lifetime()) {
                         results.push(ValidationResult::fail(format!(
-                            "The service '{}' has a singleton lifetime, \
-                             but its {}dependency '{}' has a scoped lifetime",
+                            "The service '{}' has a {} lifetime, \
+                             but its {}dependency '{}' has a {} lifetime",
                             descriptor.implementation_type().name(),
+                            lifetime.name(),
                             level,
-                            next.service_type().name()
+                            next.service_type().name(),
+                            next.lifetime().name()
                         )));
                     }
                 }
@@ -195,6 +225,38 @@ impl<'a> ValidationRule<'a> for SingletonDependsOnScoped<'a> {
     }
 }
 
+fn validate_order(services: &ServiceCollection, results: &mut Vec<ValidationResult>) {
+    let mut positions = HashMap::new();
+    let mut groups: Vec<Vec<&ServiceDescriptor>> = Vec::new();
+
+    for descriptor in services {
+        let position = *positions
+            .entry(descriptor.service_type())
+            .or_insert_with(|| {
+                groups.push(Vec::new());
+                groups.len() - 1
+            });
+        groups[position].push(descriptor);
+    }
+
+    for group in groups.iter().filter(|g| g.len() > 1) {
+        let (_, conflicts) = sort(group);
+
+        if !conflicts.is_empty() {
+            let names: Vec<_> = conflicts
+                .iter()
+                .map(|i| group[*i].implementation_type().name())
+                .collect();
+
+            results.push(ValidationResult::fail(format!(
+                "The services registered for '{}' have conflicting order constraints: {}",
+                group[0].service_type(),
+                names.join(", ")
+            )));
+        }
+    }
+}
+
 /// Validates the specified [service collection](struct.ServiceCollection.html).
 ///
 /// # Arguments
@@ -207,11 +269,15 @@ pub fn validate(services: &ServiceCollection) -> Result<(), ValidationError> {
         lookup.insert(services[i].service_type(), &services[i]);
     }
 
-    let mut results = Vec::new();
+    let mut results: Vec<_> = services
+        .binding_errors
+        .iter()
+        .map(ValidationResult::fail)
+        .collect();
     let missing_type = MissingRequiredType::new(&lookup);
     let circular_dep = CircularDependency::new(&lookup);
-    let scoped_in_singleton = SingletonDependsOnScoped::new(&lookup);
-    let rules: Vec<&dyn ValidationRule> = vec![&missing_type, &circular_dep, &scoped_in_singleton];
+    let captive_dep = CaptiveDependency::new(&lookup);
+    let rules: Vec<&dyn ValidationRule> = vec![&missing_type, &circular_dep, &captive_dep];
 
     for descriptor in services {
         for rule in &rules {
@@ -219,6 +285,8 @@ pub fn validate(services: &ServiceCollection) -> Result<(), ValidationError> {
         }
     }
 
+    validate_order(services, &mut results);
+
     if results.is_empty() {
         Ok(())
     } else {
@@ -301,6 +369,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn validate_should_allow_circular_dependency_through_lazy_dependency() {
+        // arrange
+        struct A;
+        struct B;
+
+        let mut services = ServiceCollection::new();
+
+        services
+            .add(
+                transient_as_self::<A>()
+                    .depends_on(lazy_exactly_one::<B>())
+                    .from(|_| ServiceRef::new(A {})),
+            )
+            .add(
+                transient_as_self::<B>()
+                    .depends_on(exactly_one::<A>())
+                    .from(|_| ServiceRef::new(B {})),
+            );
+
+        // act
+        let result = validate(&services);
+
+        // assert
+        assert!(result.is_ok());
+    }
+
     #[test]
     fn validate_should_report_multiple_issues() {
         // arrange
@@ -454,4 +549,184 @@ mod tests {
         // assert
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn validate_should_report_conflicting_order_constraints() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services
+            .add(
+                singleton::<dyn TestService, TestServiceImpl>()
+                    .before::<TestService2Impl>()
+                    .from(|_| ServiceRef::new(TestServiceImpl::default())),
+            )
+            .add(
+                singleton::<dyn TestService, TestService2Impl>()
+                    .before::<TestServiceImpl>()
+                    .from(|_| ServiceRef::new(TestService2Impl::default())),
+            );
+
+        // act
+        let result = validate(&services);
+
+        // assert
+        assert_eq!(
+            &result.err().unwrap().to_string(),
+            "The services registered for 'dyn di::test::TestService' have conflicting order constraints: di::test::TestServiceImpl, di::test::TestService2Impl"
+        );
+    }
+
+    #[test]
+    fn validate_should_report_tenant_service_in_singleton() {
+        // arrange
+        let services = new_dependent_services(ServiceLifetime::Tenant, ServiceLifetime::Singleton);
+
+        // act
+        let result = validate(&services);
+
+        // assert
+        assert_eq!(
+            &result.err().unwrap().to_string(),
+            "The service 'di::test::OtherTestServiceImpl' has a singleton lifetime, \
+             but its dependency 'dyn di::test::TestService' has a tenant lifetime"
+        );
+    }
+
+    #[test]
+    fn validate_should_report_scoped_service_in_tenant() {
+        // arrange
+        let services = new_dependent_services(ServiceLifetime::Scoped, ServiceLifetime::Tenant);
+
+        // act
+        let result = validate(&services);
+
+        // assert
+        assert_eq!(
+            &result.err().unwrap().to_string(),
+            "The service 'di::test::OtherTestServiceImpl' has a tenant lifetime, \
+             but its dependency 'dyn di::test::TestService' has a scoped lifetime"
+        );
+    }
+
+    #[test]
+    fn validate_should_report_per_thread_service_in_singleton() {
+        // arrange
+        let services =
+            new_dependent_services(ServiceLifetime::PerThread, ServiceLifetime::Singleton);
+
+        // act
+        let result = validate(&services);
+
+        // assert
+        assert_eq!(
+            &result.err().unwrap().to_string(),
+            "The service 'di::test::OtherTestServiceImpl' has a singleton lifetime, \
+             but its dependency 'dyn di::test::TestService' has a per-thread lifetime"
+        );
+    }
+
+    #[test]
+    fn validate_should_report_per_thread_service_in_scoped() {
+        // arrange
+        let services = new_dependent_services(ServiceLifetime::PerThread, ServiceLifetime::Scoped);
+
+        // act
+        let result = validate(&services);
+
+        // assert
+        assert_eq!(
+            &result.err().unwrap().to_string(),
+            "The service 'di::test::OtherTestServiceImpl' has a scoped lifetime, \
+             but its dependency 'dyn di::test::TestService' has a per-thread lifetime"
+        );
+    }
+
+    #[test]
+    fn validate_should_report_tenant_service_in_per_thread() {
+        // arrange
+        let services = new_dependent_services(ServiceLifetime::Tenant, ServiceLifetime::PerThread);
+
+        // act
+        let result = validate(&services);
+
+        // assert
+        assert_eq!(
+            &result.err().unwrap().to_string(),
+            "The service 'di::test::OtherTestServiceImpl' has a per-thread lifetime, \
+             but its dependency 'dyn di::test::TestService' has a tenant lifetime"
+        );
+    }
+
+    #[test]
+    fn validate_should_not_report_singleton_service_in_per_thread() {
+        // arrange
+        let services =
+            new_dependent_services(ServiceLifetime::Singleton, ServiceLifetime::PerThread);
+
+        // act
+        let result = validate(&services);
+
+        // assert
+        assert!(result.is_ok());
+    }
+
+    struct Request;
+
+    impl LifetimeStrategy for Request {
+        fn name(&self) -> &str {
+            "request"
+        }
+
+        fn width(&self) -> usize {
+            150
+        }
+
+        fn get(
+            &self,
+            _descriptor: &ServiceDescriptor,
+            _services: &ServiceProvider,
+            create: &dyn Fn() -> ServiceRef<dyn std::any::Any>,
+        ) -> ServiceRef<dyn std::any::Any> {
+            create()
+        }
+    }
+
+    static REQUEST: Request = Request;
+
+    #[test]
+    fn validate_should_report_custom_service_in_singleton() {
+        // arrange
+        let services = new_dependent_services(
+            ServiceLifetime::Custom(&REQUEST),
+            ServiceLifetime::Singleton,
+        );
+
+        // act
+        let result = validate(&services);
+
+        // assert
+        assert_eq!(
+            &result.err().unwrap().to_string(),
+            "The service 'di::test::OtherTestServiceImpl' has a singleton lifetime, \
+             but its dependency 'dyn di::test::TestService' has a request lifetime"
+        );
+    }
+
+    #[test]
+    fn validate_should_report_scoped_service_in_custom_service() {
+        // arrange
+        let services =
+            new_dependent_services(ServiceLifetime::Scoped, ServiceLifetime::Custom(&REQUEST));
+
+        // act
+        let result = validate(&services);
+
+        // assert
+        assert_eq!(
+            &result.err().unwrap().to_string(),
+            "The service 'di::test::OtherTestServiceImpl' has a request lifetime, \
+             but its dependency 'dyn di::test::TestService' has a scoped lifetime"
+        );
+    }
 }
@@ -1,5 +1,6 @@
 extern crate proc_macro;
 use proc_macro2::{Ident, Span, TokenStream};
+use proc_macro_crate::{crate_name, FoundCrate};
 use quote::quote;
 use syn::{
     parse::{Parse, ParseStream},
@@ -12,31 +13,151 @@ struct ArgContext<'a> {
     optional: bool,
     many: bool,
     lazy: bool,
+    factory: bool,
 }
 
 impl<'a> ArgContext<'a> {
-    fn new(type_: &'a TypePath, optional: bool, many: bool, lazy: bool) -> Self {
+    fn new(type_: &'a TypePath, optional: bool, many: bool, lazy: bool, factory: bool) -> Self {
         Self {
             type_,
             optional,
             many,
-            lazy,
+            lazy: lazy || factory,
+            factory,
         }
     }
 
     fn optional_of_many(&self) -> bool {
         self.optional && self.many
     }
+
+    fn lazy_fn(&self, cardinality: &str) -> Ident {
+        if self.factory {
+            Ident::new(&format!("{}_factory", cardinality), Span::call_site())
+        } else {
+            Ident::new(cardinality, Span::call_site())
+        }
+    }
+}
+
+const OPTION: &[&str] = &["std::option", "core::option"];
+const VEC: &[&str] = &["std::vec", "alloc::vec"];
+const RC: &[&str] = &["std::rc", "alloc::rc"];
+const ARC: &[&str] = &["std::sync", "alloc::sync"];
+const LAZY: &str = "lazy";
+
+enum InjectOption {
+    Service,
+    Default,
+    Value(Expr),
+    From(Path),
+    Arg,
+}
+
+impl InjectOption {
+    fn is_value(&self) -> bool {
+        !matches!(self, Self::Service)
+    }
+}
+
+impl Parse for InjectOption {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let name = input.parse::<Ident>()?;
+
+        if name == "service" {
+            Ok(Self::Service)
+        } else if name == "default" {
+            Ok(Self::Default)
+        } else if name == "value" {
+            input.parse::<Token![=]>()?;
+            Ok(Self::Value(input.parse()?))
+        } else if name == "from" {
+            input.parse::<Token![=]>()?;
+            Ok(Self::From(input.parse::<LitStr>()?.parse()?))
+        } else if name == "arg" {
+            Ok(Self::Arg)
+        } else {
+            Err(Error::new(
+                name.span(),
+                format!("Unsupported #[inject] option '{}'.", name),
+            ))
+        }
+    }
 }
 
 struct InjectableAttribute {
     trait_: Option<Path>,
+    crate_: Option<Path>,
+    order: Option<i32>,
+}
+
+impl InjectableAttribute {
+    fn parse_crate(input: ParseStream) -> Result<Path> {
+        input.parse::<Token![crate]>()?;
+        input.parse::<Token![=]>()?;
+        input.parse::<LitStr>()?.parse()
+    }
+
+    fn parse_order(input: ParseStream) -> Result<i32> {
+        input.parse::<Ident>()?;
+        input.parse::<Token![=]>()?;
+
+        let negative = input.parse::<Option<Token![-]>>()?.is_some();
+        let order = input.parse::<LitInt>()?.base10_parse::<i32>()?;
+
+        Ok(if negative { -order } else { order })
+    }
+
+    fn is_option(input: ParseStream) -> bool {
+        (input.peek(Token![crate]) || input.peek(Ident)) && input.peek2(Token![=])
+    }
 }
 
 impl Parse for InjectableAttribute {
     fn parse(input: ParseStream) -> Result<Self> {
+        let mut trait_ = None;
+        let mut crate_ = None;
+        let mut order = None;
+
+        if !input.is_empty() && !Self::is_option(input) {
+            trait_ = Some(input.parse()?);
+
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        while !input.is_empty() {
+            if input.peek(Token![crate]) {
+                crate_ = Some(Self::parse_crate(input)?);
+            } else if input.peek(Ident) && input.peek2(Token![=]) {
+                let name = input.fork().parse::<Ident>()?;
+
+                if name == "order" {
+                    order = Some(Self::parse_order(input)?);
+                } else {
+                    return Err(Error::new(
+                        name.span(),
+                        format!("Unsupported #[injectable] option '{}'.", name),
+                    ));
+                }
+            } else {
+                return Err(input.error("Unexpected token."));
+            }
+
+            if !input.is_empty() {
+                if input.peek(Token![,]) {
+                    input.parse::<Token![,]>()?;
+                } else {
+                    return Err(input.error("Unexpected token."));
+                }
+            }
+        }
+
         Ok(Self {
-            trait_: input.parse().ok(),
+            trait_,
+            crate_,
+            order,
         })
     }
 }
@@ -63,6 +184,8 @@ pub fn inject(
 /// # Arguments
 ///
 /// * `trait` - the optional name of the trait the implementation satisfies.
+/// * `crate` - the optional path to the `di` crate used by the generated code.
+/// * `order` - the optional order of the service relative to other services of the same type.
 ///
 /// # Remarks
 ///
@@ -74,6 +197,11 @@ pub fn inject(
 /// If `trait` is not specified, then the implementation will
 /// injectable as the defining struct itself.
 ///
+/// If `crate` is not specified, then the path to the `di` crate is
+/// resolved from the dependencies of the calling crate, which accounts
+/// for the `more-di` package being renamed. Specify `crate` explicitly
+/// when the `di` crate is re-exported from another crate.
+///
 /// The injected call site arguments are restricted to the same return
 /// values supported by `ServiceProvider`, which can only be:
 ///
@@ -86,6 +214,23 @@ pub fn inject(
 /// on whether the **async** feature is activated; therefore, `Rc<T>`
 /// and `Arc<T>` are also allowed any place `ServiceRef<T>` is allowed.
 ///
+/// If the **lazy** feature is activated, then each of the service
+/// arguments can also be wrapped with `Lazy` or `Factory`.
+///
+/// Types can be fully qualified, such as `std::rc::Rc<T>` or
+/// `di::ServiceRef<T>`. A type alias of `ServiceRef<T>` cannot be
+/// inspected and must be decorated with `#[inject(service)]`.
+///
+/// Arguments that are not services can be supplied by decorating
+/// the argument with one of the following:
+///
+/// * `#[inject(default)]` - uses `Default::default()`
+/// * `#[inject(value = expr)]` - uses the specified expression
+/// * `#[inject(from = "path::to::fn")]` - invokes the specified
+///   function with the signature `fn(&ServiceProvider) -> T`
+///
+/// Values are not added as service dependencies.
+///
 /// # Examples
 ///
 /// Injecting a struct as a trait.
@@ -143,6 +288,57 @@ pub fn inject(
 ///         Self { bar }
 ///     }
 /// }
+/// ```
+///
+/// Supply arguments that are not services.
+///
+/// ```
+/// pub struct Server {
+///     port: u16,
+///     timeout: Duration,
+///     name: String,
+/// }
+///
+/// fn server_name(_services: &ServiceProvider) -> String {
+///     String::from("localhost")
+/// }
+///
+/// #[injectable]
+/// impl Server {
+///     pub fn new(
+///         #[inject(value = 8080)] port: u16,
+///         #[inject(default)] timeout: Duration,
+///         #[inject(from = "server_name")] name: String) -> Self {
+///         Self { port, timeout, name }
+///     }
+/// }
+/// ```
+///
+/// Use the `di` crate re-exported from another crate.
+///
+/// ```
+/// pub struct Foo;
+///
+/// #[injectable(crate = "my_facade::di")]
+/// impl Foo {
+///     pub fn new() -> Self {
+///         Self {}
+///     }
+/// }
+/// ```
+///
+/// Resolve a handler before other handlers of the same service type.
+///
+/// ```
+/// pub struct Authenticate;
+///
+/// #[injectable(Handler, order = -10)]
+/// impl Authenticate {
+///     pub fn new() -> Self {
+///         Self {}
+///     }
+/// }
+/// ```
 #[proc_macro_attribute]
 pub fn injectable(
     metadata: proc_macro::TokenStream,
@@ -155,19 +351,35 @@ pub fn injectable(
 }
 
 fn _injectable(metadata: TokenStream, input: TokenStream) -> TokenStream {
-    let mut original = TokenStream::from(input.clone());
+    let mut original = input.clone();
     let result = match parse2::<InjectableAttribute>(metadata) {
         Ok(attribute) => {
-            if let Ok(impl_) = parse2::<ItemImpl>(TokenStream::from(input)) {
+            if let Ok(impl_) = parse2::<ItemImpl>(input) {
                 if let Type::Path(type_) = &*impl_.self_ty {
                     let implementation = &type_.path;
                     let service = attribute.trait_.as_ref().unwrap_or(implementation);
+                    let crate_ = attribute.crate_.unwrap_or_else(resolve_crate_path);
 
                     match get_injected_method(&impl_, implementation) {
                         Ok(method) => {
-                            match implement_injectable(&impl_, implementation, &service, method) {
+                            match implement_injectable(
+                                &impl_,
+                                implementation,
+                                service,
+                                method,
+                                &crate_,
+                                attribute.order,
+                            ) {
                                 Ok(trait_impl) => {
-                                    original.extend(trait_impl.into_iter());
+                                    // #[inject] is not allowed on function arguments so it
+                                    // must be removed from the original implementation
+                                    let mut stripped = impl_.clone();
+
+                                    if strip_inject_options(&mut stripped) {
+                                        original = quote! { #stripped };
+                                    }
+
+                                    original.extend(trait_impl);
                                     Ok(original)
                                 }
                                 Err(error) => Err(error),
@@ -176,7 +388,10 @@ fn _injectable(metadata: TokenStream, input: TokenStream) -> TokenStream {
                         Err(error) => Err(error),
                     }
                 } else {
-                    Err(Error::new(impl_.span(), "Expected implementation type."))
+                    Err(Error::new_spanned(
+                        &impl_.self_ty,
+                        "Expected implementation type.",
+                    ))
                 }
             } else {
                 Err(Error::new(
@@ -190,7 +405,20 @@ fn _injectable(metadata: TokenStream, input: TokenStream) -> TokenStream {
 
     match result {
         Ok(output) => output,
-        Err(error) => error.to_compile_error().into(),
+        Err(error) => error.to_compile_error(),
+    }
+}
+
+fn resolve_crate_path() -> Path {
+    // note: the library name of the 'more-di' package is 'di'. proc-macro-crate only knows the
+    // package name so it will report 'more_di' unless the dependency has been renamed.
+    match crate_name("more-di") {
+        Ok(FoundCrate::Itself) => parse_quote! { crate },
+        Ok(FoundCrate::Name(name)) if name != "more_di" => {
+            let ident = Ident::new(&name, Span::call_site());
+            parse_quote! { #ident }
+        }
+        _ => parse_quote! { di },
     }
 }
 
@@ -199,27 +427,105 @@ fn implement_injectable(
     implementation: &Path,
     service: &Path,
     method: &Signature,
+    crate_: &Path,
+    order: Option<i32>,
 ) -> Result<TokenStream> {
-    let (args, deps) = inject_argument_call_sites(method)?;
+    let call_site = inject_argument_call_sites(method, crate_)?;
+    let args = &call_site.args;
+    let deps = &call_site.deps;
     let fn_ = &method.ident;
     let is_trait =
         implementation.segments.last().unwrap().ident != service.segments.last().unwrap().ident;
+    let depends_on = quote! { #(.depends_on(#deps))* };
+    let order = order_option(order);
+    let generics = &impl_.generics;
+    let where_ = &generics.where_clause;
+
+    if !call_site.params.is_empty() {
+        return Ok(implement_param_injectable(
+            impl_,
+            implementation,
+            service,
+            method,
+            crate_,
+            &call_site,
+            order,
+        ));
+    }
+
     let new = if is_trait {
-        quote! { di::ServiceDescriptorBuilder::<dyn #service, Self>::new(lifetime, di::Type::of::<Self>()) }
+        quote! { #crate_::ServiceDescriptorBuilder::<dyn #service, Self>::new(lifetime, #crate_::Type::of::<Self>()) }
+    } else {
+        quote! { #crate_::ServiceDescriptorBuilder::<Self, Self>::new(lifetime, #crate_::Type::of::<Self>()) }
+    };
+    let code = quote! {
+        impl#generics #crate_::Injectable for #implementation #where_ {
+            #[track_caller]
+            fn inject(lifetime: #crate_::ServiceLifetime) -> #crate_::ServiceDescriptor {
+                #new#depends_on#order.from(|sp: &#crate_::ServiceProvider| #crate_::ServiceRef::new(Self::#fn_(#(#args),*)))
+            }
+        }
+    };
+    Ok(code)
+}
+
+fn implement_param_injectable(
+    impl_: &ItemImpl,
+    implementation: &Path,
+    service: &Path,
+    method: &Signature,
+    crate_: &Path,
+    call_site: &CallSite,
+    order: Option<TokenStream>,
+) -> TokenStream {
+    let args = &call_site.args;
+    let deps = &call_site.deps;
+    let fn_ = &method.ident;
+    let is_trait =
+        implementation.segments.last().unwrap().ident != service.segments.last().unwrap().ident;
+    let service = if is_trait {
+        quote! { dyn #service }
+    } else {
+        quote! { Self }
+    };
+    let names = call_site.params.iter().map(|p| &p.0);
+    let types = call_site.params.iter().map(|p| p.1);
+
+    // multiple runtime arguments are represented as a tuple
+    let (params, param_types) = if call_site.params.len() == 1 {
+        (quote! { #(#names)* }, quote! { #(#types)* })
     } else {
-        quote! { di::ServiceDescriptorBuilder::<Self, Self>::new(lifetime, di::Type::of::<Self>()) }
+        (quote! { (#(#names),*) }, quote! { (#(#types),*) })
     };
     let depends_on = quote! { #(.depends_on(#deps))* };
     let generics = &impl_.generics;
     let where_ = &generics.where_clause;
-    let code = quote! {
-        impl#generics di::Injectable for #implementation #where_ {
-            fn inject(lifetime: di::ServiceLifetime) -> di::ServiceDescriptor {
-                #new#depends_on.from(|sp: &di::ServiceProvider| di::ServiceRef::new(Self::#fn_(#(#args),*)))
+
+    quote! {
+        impl#generics #crate_::ParamInjectable for #implementation #where_ {
+            #[track_caller]
+            fn inject(lifetime: #crate_::ServiceLifetime) -> #crate_::ServiceDescriptor {
+                assert!(
+                    lifetime == #crate_::ServiceLifetime::Transient || lifetime == #crate_::ServiceLifetime::Scoped,
+                    "The factory for '{}' must have a transient or scoped lifetime.",
+                    ::std::any::type_name::<Self>()
+                );
+                let factory: #crate_::ServiceRef<#crate_::ParamServiceFactory<#param_types, #service>> =
+                    #crate_::ServiceRef::new(|sp: &#crate_::ServiceProvider, #params: #param_types| -> #crate_::ServiceRef<#service> {
+                        #crate_::ServiceRef::new(Self::#fn_(#(#args),*))
+                    });
+                #crate_::ServiceDescriptorBuilder::<#crate_::ParamFactory<#param_types, #service>, Self>::new(lifetime, #crate_::Type::of::<Self>())#depends_on#order
+                    .from(move |sp: &#crate_::ServiceProvider| #crate_::ServiceRef::new(#crate_::ParamFactory::new(sp.clone(), factory.clone())))
             }
         }
-    };
-    Ok(code.into())
+    }
+}
+
+fn order_option(order: Option<i32>) -> Option<TokenStream> {
+    order.map(|order| {
+        let order = proc_macro2::Literal::i32_unsuffixed(order);
+        quote! { .order(#order) }
+    })
 }
 
 fn get_injected_method<'a>(impl_: &'a ItemImpl, path: &Path) -> Result<&'a Signature> {
@@ -231,7 +537,7 @@ fn get_injected_method<'a>(impl_: &'a ItemImpl, path: &Path) -> Result<&'a Signa
         if let ImplItem::Method(method) = item {
             let signature = &method.sig;
 
-            if method.attrs.iter().any(|a| a.path.is_ident("inject")) {
+            if method.attrs.iter().any(is_inject) {
                 methods.push(signature);
             }
 
@@ -246,8 +552,8 @@ fn get_injected_method<'a>(impl_: &'a ItemImpl, path: &Path) -> Result<&'a Signa
             if let Some(method) = convention {
                 Ok(method)
             } else {
-                Err(Error::new(
-                    impl_.span(),
+                Err(Error::new_spanned(
+                    &impl_.self_ty,
                     format!(
                         "Neither {}::new or an associated method decorated with #[inject] was found.",
                         path.segments.last().unwrap().ident
@@ -256,8 +562,8 @@ fn get_injected_method<'a>(impl_: &'a ItemImpl, path: &Path) -> Result<&'a Signa
             }
         }
         1 => Ok(methods[0]),
-        _ => Err(Error::new(
-            impl_.span(),
+        _ => Err(Error::new_spanned(
+            &impl_.self_ty,
             format!(
                 "{} has more than one associated method decorated with #[inject].",
                 path.segments.last().unwrap().ident
@@ -266,181 +572,332 @@ fn get_injected_method<'a>(impl_: &'a ItemImpl, path: &Path) -> Result<&'a Signa
     }
 }
 
-fn inject_argument_call_sites(method: &Signature) -> Result<(Vec<TokenStream>, Vec<TokenStream>)> {
+struct CallSite<'a> {
+    args: Vec<TokenStream>,
+    deps: Vec<TokenStream>,
+    params: Vec<(Ident, &'a Type)>,
+}
+
+fn inject_argument_call_sites<'a>(method: &'a Signature, crate_: &Path) -> Result<CallSite<'a>> {
     let count = method.inputs.len();
+    let mut call_site = CallSite {
+        args: Vec::with_capacity(count),
+        deps: Vec::with_capacity(count),
+        params: Vec::new(),
+    };
 
     if count == 0 {
-        return Ok((Vec::with_capacity(0), Vec::with_capacity(0)));
+        return Ok(call_site);
     }
 
-    let mut args = Vec::with_capacity(count);
-    let mut deps = Vec::with_capacity(count);
+    let mut errors: Option<Error> = None;
+
+    for (index, input) in method.inputs.iter().enumerate() {
+        let result = match input {
+            FnArg::Typed(arg) => resolve_type(arg, index, crate_).map(|(call, dep)| {
+                if is_param(arg) {
+                    call_site.params.push((param_ident(index), &arg.ty));
+                }
 
-    for input in method.inputs.iter() {
-        let (arg, dep) = match input {
-            FnArg::Typed(type_) => resolve_type(&*type_.ty)?,
-            _ => return Err(Error::new(
-                input.span(),
+                (call, dep)
+            }),
+            _ => Err(Error::new_spanned(
+                input,
                 "The argument must be ServiceRef, Rc, or Arc and optionally wrapped with Option or Vec.")),
         };
 
-        args.push(arg);
+        // report every invalid argument rather than stopping at the first one
+        match result {
+            Ok((arg, dep)) => {
+                call_site.args.push(arg);
+
+                if let Some(d) = dep {
+                    call_site.deps.push(d);
+                }
+            }
+            Err(error) => match errors.as_mut() {
+                Some(errors) => errors.combine(error),
+                _ => errors = Some(error),
+            },
+        }
+    }
+
+    if let Some(error) = errors {
+        Err(error)
+    } else {
+        Ok(call_site)
+    }
+}
+
+fn param_ident(index: usize) -> Ident {
+    Ident::new(&format!("arg{}", index), Span::call_site())
+}
+
+fn is_param(arg: &PatType) -> bool {
+    get_inject_options(&arg.attrs)
+        .map(|options| options.iter().any(|o| matches!(o, InjectOption::Arg)))
+        .unwrap_or(false)
+}
+
+fn is_inject(attr: &Attribute) -> bool {
+    attr.path.segments.last().unwrap().ident == "inject"
+}
+
+fn get_inject_options(attrs: &[Attribute]) -> Result<Vec<InjectOption>> {
+    let mut options = Vec::new();
+
+    for attr in attrs.iter().filter(|a| is_inject(a)) {
+        options.push(attr.parse_args()?);
+    }
+
+    Ok(options)
+}
+
+fn strip_inject_options(impl_: &mut ItemImpl) -> bool {
+    let mut stripped = false;
 
-        if let Some(d) = dep {
-            deps.push(d);
+    for item in impl_.items.iter_mut() {
+        if let ImplItem::Method(method) = item {
+            for input in method.sig.inputs.iter_mut() {
+                if let FnArg::Typed(arg) = input {
+                    let count = arg.attrs.len();
+                    arg.attrs.retain(|a| !is_inject(a));
+                    stripped |= count != arg.attrs.len();
+                }
+            }
         }
     }
 
-    Ok((args, deps))
+    stripped
 }
 
 fn new_arg_context(arg: &Type) -> Result<ArgContext<'_>> {
     if let Type::Path(outer) = arg {
-        let (type_, lazy) = if let Some(inner) = get_generic_type_arg(outer, "Lazy") {
+        let (type_, lazy, factory) = if let Some(inner) = get_lazy_type_arg(outer, "Lazy") {
+            match inner {
+                Type::Path(path) => (path, true, false),
+                _ => (outer, false, false),
+            }
+        } else if let Some(inner) = get_lazy_type_arg(outer, "Factory") {
             match inner {
-                Type::Path(path) => (path, true),
-                _ => (outer, false),
+                Type::Path(path) => (path, false, true),
+                _ => (outer, false, false),
             }
         } else {
-            (outer, false)
+            (outer, false, false)
         };
 
-        if let Some(inner) = get_generic_type_arg(type_, "Option") {
+        if let Some(inner) = get_generic_type_arg(type_, "Option", Some(OPTION)) {
             if let Type::Path(path) = inner {
-                Ok(ArgContext::new(path, true, false, lazy))
+                let many = get_generic_type_arg(path, "Vec", Some(VEC)).is_some();
+                Ok(ArgContext::new(path, true, many, lazy, factory))
             } else {
-                Err(Error::new(inner.span(), "Expected ServiceRef, Rc, or Arc."))
+                Err(Error::new_spanned(
+                    inner,
+                    "Expected ServiceRef, Rc, or Arc.",
+                ))
             }
-        } else if let Some(inner) = get_generic_type_arg(type_, "Vec") {
+        } else if let Some(inner) = get_generic_type_arg(type_, "Vec", Some(VEC)) {
             if let Type::Path(path) = inner {
-                Ok(ArgContext::new(path, false, true, lazy))
+                Ok(ArgContext::new(path, false, true, lazy, factory))
             } else {
-                Err(Error::new(inner.span(), "Expected ServiceRef, Rc, or Arc."))
+                Err(Error::new_spanned(
+                    inner,
+                    "Expected ServiceRef, Rc, or Arc.",
+                ))
             }
         } else {
-            Ok(ArgContext::new(type_, false, false, lazy))
+            Ok(ArgContext::new(type_, false, false, lazy, factory))
         }
     } else {
-        Err(Error::new(arg.span(), "Expected type path."))
+        Err(Error::new_spanned(arg, "Expected type path."))
     }
 }
 
-fn resolve_trait_type(
-    trait_: &TypeTraitObject,
+fn resolve_service_type(
+    service: TokenStream,
     context: &ArgContext,
+    crate_: &Path,
 ) -> (TokenStream, Option<TokenStream>) {
-    if context.optional {
+    let (arg, cardinality) = if context.optional {
         (
             if context.lazy {
-                quote! { di::lazy::zero_or_one::<#trait_>(sp.clone()) }
+                let fn_ = context.lazy_fn("zero_or_one");
+                quote! { #crate_::lazy::#fn_::<#service>(sp.clone()) }
             } else {
-                quote! { sp.get::<#trait_>() }
+                quote! { sp.get::<#service>() }
             },
-            Some(
-                quote! { di::ServiceDependency::new(di::Type::of::<#trait_>(), di::ServiceCardinality::ZeroOrOne) },
-            ),
+            quote! { ZeroOrOne },
         )
     } else if context.many {
         (
             if context.lazy {
-                quote! { di::lazy::zero_or_more::<#trait_>(sp.clone()) }
+                let fn_ = context.lazy_fn("zero_or_more");
+                quote! { #crate_::lazy::#fn_::<#service>(sp.clone()) }
             } else {
-                quote! { sp.get_all::<#trait_>().collect() }
+                quote! { sp.get_all::<#service>().collect() }
             },
-            Some(
-                quote! { di::ServiceDependency::new(di::Type::of::<#trait_>(), di::ServiceCardinality::ZeroOrMore) },
-            ),
+            quote! { ZeroOrMore },
         )
     } else {
         (
             if context.lazy {
-                quote! { di::lazy::exactly_one::<#trait_>(sp.clone()) }
+                let fn_ = context.lazy_fn("exactly_one");
+                quote! { #crate_::lazy::#fn_::<#service>(sp.clone()) }
             } else {
-                quote! { sp.get_required::<#trait_>() }
+                quote! { sp.get_required::<#service>() }
             },
-            Some(
-                quote! { di::ServiceDependency::new(di::Type::of::<#trait_>(), di::ServiceCardinality::ExactlyOne) },
-            ),
+            quote! { ExactlyOne },
         )
-    }
+    };
+    let new = if context.lazy {
+        quote! { new_lazy }
+    } else {
+        quote! { new }
+    };
+    let dep = quote! {
+        #crate_::ServiceDependency::#new(#crate_::Type::of::<#service>(), #crate_::ServiceCardinality::#cardinality)
+    };
+
+    (arg, Some(dep))
 }
 
-fn resolve_struct_type(
-    struct_: &TypePath,
-    context: &ArgContext,
-) -> (TokenStream, Option<TokenStream>) {
-    if context.optional {
-        (
-            if context.lazy {
-                quote! { di::lazy::zero_or_one::<#struct_>(sp.clone()) }
-            } else {
-                quote! { sp.get::<#struct_>() }
-            },
-            Some(
-                quote! { di::ServiceDependency::new(di::Type::of::<#struct_>(), di::ServiceCardinality::ZeroOrOne) },
-            ),
-        )
-    } else if context.many {
-        (
-            if context.lazy {
-                quote! { di::lazy::zero_or_more::<#struct_>(sp.clone()) }
-            } else {
-                quote! { sp.get_all::<#struct_>().collect() }
-            },
-            Some(
-                quote! { di::ServiceDependency::new(di::Type::of::<#struct_>(), di::ServiceCardinality::ZeroOrMore) },
-            ),
-        )
-    } else {
-        (
-            if context.lazy {
-                quote! { di::lazy::exactly_one::<#struct_>(sp.clone()) }
-            } else {
-                quote! { sp.get_required::<#struct_>() }
+fn resolve_type(
+    arg: &PatType,
+    index: usize,
+    crate_: &Path,
+) -> Result<(TokenStream, Option<TokenStream>)> {
+    let options = get_inject_options(&arg.attrs)?;
+    let mut values = options.iter().filter(|o| o.is_value());
+
+    if let Some(value) = values.next() {
+        if values.next().is_some() || options.len() > 1 {
+            return Err(Error::new_spanned(
+                arg,
+                "Only one of #[inject(service)], #[inject(default)], #[inject(value = ...)], \
+                 #[inject(from = ...)], or #[inject(arg)] can be specified.",
+            ));
+        }
+
+        // values are not services and, therefore, are not dependencies
+        return Ok((
+            match value {
+                InjectOption::Value(expr) => quote! { #expr },
+                InjectOption::From(path) => quote! { #path(sp) },
+                InjectOption::Arg => {
+                    let param = param_ident(index);
+                    quote! { #param }
+                }
+                _ => quote! { ::std::default::Default::default() },
             },
-            Some(
-                quote! { di::ServiceDependency::new(di::Type::of::<#struct_>(), di::ServiceCardinality::ExactlyOne) },
-            ),
-        )
+            None,
+        ));
     }
-}
 
-fn resolve_type(arg: &Type) -> Result<(TokenStream, Option<TokenStream>)> {
-    let context = new_arg_context(arg)?;
+    let service = options.iter().any(|o| matches!(o, InjectOption::Service));
+    let context = new_arg_context(&arg.ty)?;
 
-    if let Some(inner_type) = get_generic_type_arg(context.type_, "ServiceRef")
-        .or(get_generic_type_arg(context.type_, "Rc"))
-        .or(get_generic_type_arg(context.type_, "Arc"))
-    {
-        if context.optional_of_many() {
-            return Err(Error::new(
-                arg.span(),
-                "Option<Vec> is not supported. Did you mean Vec?",
-            ));
-        }
+    if context.optional_of_many() {
+        return Err(Error::new_spanned(
+            &arg.ty,
+            "Option<Vec> is not supported. Did you mean Vec?",
+        ));
+    }
 
+    if let Some(inner_type) = get_service_ref_type_arg(context.type_) {
         match inner_type {
-            Type::TraitObject(trait_) => Ok(resolve_trait_type(trait_, &context)),
-            Type::Path(struct_) => Ok(resolve_struct_type(struct_, &context)),
-            _ => Err(Error::new(inner_type.span(), "Expected a trait or struct.")),
+            Type::TraitObject(trait_) => {
+                Ok(resolve_service_type(quote! { #trait_ }, &context, crate_))
+            }
+            Type::Path(struct_) => Ok(resolve_service_type(quote! { #struct_ }, &context, crate_)),
+            _ => Err(Error::new_spanned(
+                inner_type,
+                "Expected a trait or struct.",
+            )),
         }
-    } else if context.type_.path.segments.first().unwrap().ident
-        == Ident::new("ServiceProvider", Span::call_site())
+    } else if service {
+        // the type is assumed to be an alias of ServiceRef<T>, Rc<T>, or Arc<T>,
+        // all of which dereference to the injected type T
+        let alias = context.type_;
+        Ok(resolve_service_type(
+            quote! { <#alias as ::std::ops::Deref>::Target },
+            &context,
+            crate_,
+        ))
+    } else if !context.lazy
+        && !context.optional
+        && !context.many
+        && is_type(&context.type_.path, "ServiceProvider", None)
     {
         Ok((quote! { sp.clone() }, None))
     } else {
-        Err(Error::new(
-            context.type_.span(),
-            "Expected ServiceRef, Rc, or Arc.",
+        Err(Error::new_spanned(
+            &arg.ty,
+            "Expected ServiceRef, Rc, or Arc. Use #[inject(service)] for an alias or \
+             #[inject(default)], #[inject(value = ...)], or #[inject(from = ...)] for a value.",
         ))
     }
 }
 
-fn get_generic_type_arg<'a>(type_: &'a TypePath, name: &str) -> Option<&'a Type> {
+fn get_service_ref_type_arg(type_: &TypePath) -> Option<&Type> {
+    get_generic_type_arg(type_, "ServiceRef", None)
+        .or_else(|| get_generic_type_arg(type_, "Rc", Some(RC)))
+        .or_else(|| get_generic_type_arg(type_, "Arc", Some(ARC)))
+}
+
+fn get_lazy_type_arg<'a>(type_: &'a TypePath, name: &str) -> Option<&'a Type> {
+    let segments = &type_.path.segments;
+    let count = segments.len();
+
+    // the crate might be renamed or re-exported, but a qualified type must at least
+    // come from the lazy module; otherwise, it is considered a user-defined type
+    if count > 1 && segments[count - 2].ident != LAZY {
+        None
+    } else {
+        get_generic_type_arg(type_, name, None)
+    }
+}
+
+fn is_type(path: &Path, name: &str, modules: Option<&[&str]>) -> bool {
+    let count = path.segments.len();
+    let segment = path.segments.last().unwrap();
+
+    if segment.ident != name {
+        return false;
+    }
+
+    if count == 1 {
+        return true;
+    }
+
+    // types defined by di can be qualified by any path because the crate might be
+    // renamed or re-exported. types defined by the standard library must match one
+    // of their well-known modules; otherwise, they are considered user-defined types
+    if let Some(modules) = modules {
+        let module = path
+            .segments
+            .iter()
+            .take(count - 1)
+            .map(|s| s.ident.to_string())
+            .collect::<Vec<_>>()
+            .join("::");
+
+        modules.iter().any(|m| *m == module)
+    } else {
+        true
+    }
+}
+
+fn get_generic_type_arg<'a>(
+    type_: &'a TypePath,
+    name: &str,
+    modules: Option<&[&str]>,
+) -> Option<&'a Type> {
     let path = &type_.path;
-    let segment = path.segments.first().unwrap();
 
-    if segment.ident == Ident::new(name, Span::call_site()) {
+    if type_.qself.is_none() && is_type(path, name, modules) {
+        let segment = path.segments.last().unwrap();
+
         if let PathArguments::AngleBracketed(ref type_args) = segment.arguments {
             for type_arg in type_args.args.iter() {
                 if let GenericArgument::Type(ref inner_type) = type_arg {
@@ -484,7 +941,7 @@ mod test {
             "} ",
             "} ",
             "impl di :: Injectable for FooImpl { ",
-            "fn inject (lifetime : di :: ServiceLifetime) -> di :: ServiceDescriptor { ",
+            "# [track_caller] fn inject (lifetime : di :: ServiceLifetime) -> di :: ServiceDescriptor { ",
             "di :: ServiceDescriptorBuilder :: < dyn Foo , Self > :: new (lifetime , di :: Type :: of :: < Self > ()) ",
             ". from (| sp : & di :: ServiceProvider | di :: ServiceRef :: new (Self :: new ())) ",
             "} ",
@@ -521,7 +978,7 @@ mod test {
             "} ",
             "} ",
             "impl di :: Injectable for FooImpl { ",
-            "fn inject (lifetime : di :: ServiceLifetime) -> di :: ServiceDescriptor { ",
+            "# [track_caller] fn inject (lifetime : di :: ServiceLifetime) -> di :: ServiceDescriptor { ",
             "di :: ServiceDescriptorBuilder :: < dyn Foo , Self > :: new (lifetime , di :: Type :: of :: < Self > ()) ",
             ". from (| sp : & di :: ServiceProvider | di :: ServiceRef :: new (Self :: create ())) ",
             "} ",
@@ -556,7 +1013,7 @@ mod test {
             "} ",
             "} ",
             "impl di :: Injectable for FooImpl { ",
-            "fn inject (lifetime : di :: ServiceLifetime) -> di :: ServiceDescriptor { ",
+            "# [track_caller] fn inject (lifetime : di :: ServiceLifetime) -> di :: ServiceDescriptor { ",
             "di :: ServiceDescriptorBuilder :: < dyn Foo , Self > :: new (lifetime , di :: Type :: of :: < Self > ()) ",
             ". depends_on (di :: ServiceDependency :: new (di :: Type :: of :: < dyn Bar > () , di :: ServiceCardinality :: ExactlyOne)) ",
             ". from (| sp : & di :: ServiceProvider | di :: ServiceRef :: new (Self :: new (sp . get_required :: < dyn Bar > ()))) ",
@@ -592,7 +1049,7 @@ mod test {
             "} ",
             "} ",
             "impl di :: Injectable for FooImpl { ",
-            "fn inject (lifetime : di :: ServiceLifetime) -> di :: ServiceDescriptor { ",
+            "# [track_caller] fn inject (lifetime : di :: ServiceLifetime) -> di :: ServiceDescriptor { ",
             "di :: ServiceDescriptorBuilder :: < dyn Foo , Self > :: new (lifetime , di :: Type :: of :: < Self > ()) ",
             ". depends_on (di :: ServiceDependency :: new (di :: Type :: of :: < dyn Bar > () , di :: ServiceCardinality :: ZeroOrOne)) ",
             ". from (| sp : & di :: ServiceProvider | di :: ServiceRef :: new (Self :: new (sp . get :: < dyn Bar > ()))) ",
@@ -628,7 +1085,7 @@ mod test {
             "} ",
             "} ",
             "impl di :: Injectable for FooImpl { ",
-            "fn inject (lifetime : di :: ServiceLifetime) -> di :: ServiceDescriptor { ",
+            "# [track_caller] fn inject (lifetime : di :: ServiceLifetime) -> di :: ServiceDescriptor { ",
             "di :: ServiceDescriptorBuilder :: < dyn Foo , Self > :: new (lifetime , di :: Type :: of :: < Self > ()) ",
             ". depends_on (di :: ServiceDependency :: new (di :: Type :: of :: < dyn Bar > () , di :: ServiceCardinality :: ZeroOrMore)) ",
             ". from (| sp : & di :: ServiceProvider | di :: ServiceRef :: new (Self :: new (sp . get_all :: < dyn Bar > () . collect ()))) ",
@@ -666,7 +1123,7 @@ mod test {
             "} ",
             "} ",
             "impl di :: Injectable for ThingImpl { ",
-            "fn inject (lifetime : di :: ServiceLifetime) -> di :: ServiceDescriptor { ",
+            "# [track_caller] fn inject (lifetime : di :: ServiceLifetime) -> di :: ServiceDescriptor { ",
             "di :: ServiceDescriptorBuilder :: < dyn Thing , Self > :: new (lifetime , di :: Type :: of :: < Self > ()) ",
             ". depends_on (di :: ServiceDependency :: new (di :: Type :: of :: < dyn Foo > () , di :: ServiceCardinality :: ExactlyOne)) ",
             ". depends_on (di :: ServiceDependency :: new (di :: Type :: of :: < dyn Bar > () , di :: ServiceCardinality :: ZeroOrOne)) ",
@@ -703,7 +1160,7 @@ mod test {
             "} ",
             "} ",
             "impl di :: Injectable for FooImpl { ",
-            "fn inject (lifetime : di :: ServiceLifetime) -> di :: ServiceDescriptor { ",
+            "# [track_caller] fn inject (lifetime : di :: ServiceLifetime) -> di :: ServiceDescriptor { ",
             "di :: ServiceDescriptorBuilder :: < Self , Self > :: new (lifetime , di :: Type :: of :: < Self > ()) ",
             ". from (| sp : & di :: ServiceProvider | di :: ServiceRef :: new (Self :: new ())) ",
             "} ",
@@ -738,7 +1195,7 @@ mod test {
             "} ",
             "} ",
             "impl di :: Injectable for FooImpl { ",
-            "fn inject (lifetime : di :: ServiceLifetime) -> di :: ServiceDescriptor { ",
+            "# [track_caller] fn inject (lifetime : di :: ServiceLifetime) -> di :: ServiceDescriptor { ",
             "di :: ServiceDescriptorBuilder :: < dyn Foo , Self > :: new (lifetime , di :: Type :: of :: < Self > ()) ",
             ". depends_on (di :: ServiceDependency :: new (di :: Type :: of :: < Bar > () , di :: ServiceCardinality :: ExactlyOne)) ",
             ". from (| sp : & di :: ServiceProvider | di :: ServiceRef :: new (Self :: new (sp . get_required :: < Bar > ()))) ",
@@ -774,7 +1231,7 @@ mod test {
             "} ",
             "} ",
             "impl < T : Default > di :: Injectable for GenericBar < T > { ",
-            "fn inject (lifetime : di :: ServiceLifetime) -> di :: ServiceDescriptor { ",
+            "# [track_caller] fn inject (lifetime : di :: ServiceLifetime) -> di :: ServiceDescriptor { ",
             "di :: ServiceDescriptorBuilder :: < Self , Self > :: new (lifetime , di :: Type :: of :: < Self > ()) ",
             ". from (| sp : & di :: ServiceProvider | di :: ServiceRef :: new (Self :: new ())) ",
             "} ",
@@ -821,7 +1278,7 @@ mod test {
             "TKey : Debug , ",
             "TValue : Debug ",
             "{ ",
-            "fn inject (lifetime : di :: ServiceLifetime) -> di :: ServiceDescriptor { ",
+            "# [track_caller] fn inject (lifetime : di :: ServiceLifetime) -> di :: ServiceDescriptor { ",
             "di :: ServiceDescriptorBuilder :: < dyn Pair < TKey , TValue > , Self > :: new (lifetime , di :: Type :: of :: < Self > ()) ",
             ". depends_on (di :: ServiceDependency :: new (di :: Type :: of :: < TKey > () , di :: ServiceCardinality :: ExactlyOne)) ",
             ". depends_on (di :: ServiceDependency :: new (di :: Type :: of :: < TValue > () , di :: ServiceCardinality :: ExactlyOne)) ",
@@ -833,4 +1290,463 @@ mod test {
 
         assert_eq!(expected, result.to_string());
     }
+
+    #[test]
+    fn attribute_should_use_specified_crate_path() {
+        // arrange
+        let metadata = TokenStream::from_str(r#"Foo, crate = "facade::di""#).unwrap();
+        let input = TokenStream::from_str(
+            r#"
+            impl FooImpl {
+                fn new(_bar: Rc<dyn Bar>) -> Self {
+                    Self { }
+                }
+            }
+        "#,
+        )
+        .unwrap();
+
+        // act
+        let result = _injectable(metadata, input);
+
+        // assert
+        let expected = concat!(
+            "impl FooImpl { ",
+            "fn new (_bar : Rc < dyn Bar >) -> Self { ",
+            "Self { } ",
+            "} ",
+            "} ",
+            "impl facade :: di :: Injectable for FooImpl { ",
+            "# [track_caller] fn inject (lifetime : facade :: di :: ServiceLifetime) -> facade :: di :: ServiceDescriptor { ",
+            "facade :: di :: ServiceDescriptorBuilder :: < dyn Foo , Self > :: new (lifetime , facade :: di :: Type :: of :: < Self > ()) ",
+            ". depends_on (facade :: di :: ServiceDependency :: new (facade :: di :: Type :: of :: < dyn Bar > () , facade :: di :: ServiceCardinality :: ExactlyOne)) ",
+            ". from (| sp : & facade :: di :: ServiceProvider | facade :: di :: ServiceRef :: new (Self :: new (sp . get_required :: < dyn Bar > ()))) ",
+            "} ",
+            "}");
+
+        assert_eq!(expected, result.to_string());
+    }
+
+    #[test]
+    fn attribute_should_use_specified_crate_path_for_self() {
+        // arrange
+        let metadata = TokenStream::from_str(r#"crate = "facade::di""#).unwrap();
+        let input = TokenStream::from_str(
+            r#"
+            impl FooImpl {
+                fn new() -> Self {
+                    Self { }
+                }
+            }
+        "#,
+        )
+        .unwrap();
+
+        // act
+        let result = _injectable(metadata, input);
+
+        // assert
+        let expected = concat!(
+            "impl FooImpl { ",
+            "fn new () -> Self { ",
+            "Self { } ",
+            "} ",
+            "} ",
+            "impl facade :: di :: Injectable for FooImpl { ",
+            "# [track_caller] fn inject (lifetime : facade :: di :: ServiceLifetime) -> facade :: di :: ServiceDescriptor { ",
+            "facade :: di :: ServiceDescriptorBuilder :: < Self , Self > :: new (lifetime , facade :: di :: Type :: of :: < Self > ()) ",
+            ". from (| sp : & facade :: di :: ServiceProvider | facade :: di :: ServiceRef :: new (Self :: new ())) ",
+            "} ",
+            "}");
+
+        assert_eq!(expected, result.to_string());
+    }
+
+    #[test]
+    fn attribute_should_specify_order() {
+        // arrange
+        let metadata = TokenStream::from_str(r#"Foo, order = -10, crate = "facade::di""#).unwrap();
+        let input = TokenStream::from_str(
+            r#"
+            impl FooImpl {
+                fn new() -> Self {
+                    Self { }
+                }
+            }
+        "#,
+        )
+        .unwrap();
+
+        // act
+        let result = _injectable(metadata, input);
+
+        // assert
+        let expected = concat!(
+            "impl FooImpl { ",
+            "fn new () -> Self { ",
+            "Self { } ",
+            "} ",
+            "} ",
+            "impl facade :: di :: Injectable for FooImpl { ",
+            "# [track_caller] fn inject (lifetime : facade :: di :: ServiceLifetime) -> facade :: di :: ServiceDescriptor { ",
+            "facade :: di :: ServiceDescriptorBuilder :: < dyn Foo , Self > :: new (lifetime , facade :: di :: Type :: of :: < Self > ()) ",
+            ". order (- 10) ",
+            ". from (| sp : & facade :: di :: ServiceProvider | facade :: di :: ServiceRef :: new (Self :: new ())) ",
+            "} ",
+            "}");
+
+        assert_eq!(expected, result.to_string());
+    }
+
+    #[test]
+    fn attribute_should_report_unsupported_option() {
+        // arrange
+        let metadata = TokenStream::from_str(r#"Foo, priority = 10"#).unwrap();
+        let input = TokenStream::from_str(
+            r#"
+            impl FooImpl {
+                fn new() -> Self {
+                    Self { }
+                }
+            }
+        "#,
+        )
+        .unwrap();
+
+        // act
+        let result = _injectable(metadata, input);
+
+        // assert
+        assert!(result
+            .to_string()
+            .contains("Unsupported #[injectable] option 'priority'."));
+    }
+
+    #[test]
+    fn attribute_should_inject_dependencies_with_qualified_paths() {
+        // arrange
+        let metadata = TokenStream::from_str(r#"Foo"#).unwrap();
+        let input = TokenStream::from_str(
+            r#"
+            impl FooImpl {
+                fn new(
+                    _bar: std::rc::Rc<dyn Bar>,
+                    _baz: ::std::option::Option<di::ServiceRef<Baz>>,
+                    _bars: std::vec::Vec<std::sync::Arc<dyn Bar>>,
+                    _sp: di::ServiceProvider) -> Self {
+                    Self { }
+                }
+            }
+        "#,
+        )
+        .unwrap();
+
+        // act
+        let result = _injectable(metadata, input);
+
+        // assert
+        let expected = concat!(
+            "impl di :: Injectable for FooImpl { ",
+            "# [track_caller] fn inject (lifetime : di :: ServiceLifetime) -> di :: ServiceDescriptor { ",
+            "di :: ServiceDescriptorBuilder :: < dyn Foo , Self > :: new (lifetime , di :: Type :: of :: < Self > ()) ",
+            ". depends_on (di :: ServiceDependency :: new (di :: Type :: of :: < dyn Bar > () , di :: ServiceCardinality :: ExactlyOne)) ",
+            ". depends_on (di :: ServiceDependency :: new (di :: Type :: of :: < Baz > () , di :: ServiceCardinality :: ZeroOrOne)) ",
+            ". depends_on (di :: ServiceDependency :: new (di :: Type :: of :: < dyn Bar > () , di :: ServiceCardinality :: ZeroOrMore)) ",
+            ". from (| sp : & di :: ServiceProvider | di :: ServiceRef :: new (Self :: new (",
+            "sp . get_required :: < dyn Bar > () , ",
+            "sp . get :: < Baz > () , ",
+            "sp . get_all :: < dyn Bar > () . collect () , ",
+            "sp . clone ()))) ",
+            "} ",
+            "}");
+
+        assert!(result.to_string().ends_with(expected));
+    }
+
+    #[test]
+    fn attribute_should_inject_service_alias() {
+        // arrange
+        let metadata = TokenStream::from_str(r#"Foo"#).unwrap();
+        let input = TokenStream::from_str(
+            r#"
+            impl FooImpl {
+                fn new(#[inject(service)] _bar: BarRef) -> Self {
+                    Self { }
+                }
+            }
+        "#,
+        )
+        .unwrap();
+
+        // act
+        let result = _injectable(metadata, input);
+
+        // assert
+        let expected = concat!(
+            "impl FooImpl { ",
+            "fn new (_bar : BarRef) -> Self { ",
+            "Self { } ",
+            "} ",
+            "} ",
+            "impl di :: Injectable for FooImpl { ",
+            "# [track_caller] fn inject (lifetime : di :: ServiceLifetime) -> di :: ServiceDescriptor { ",
+            "di :: ServiceDescriptorBuilder :: < dyn Foo , Self > :: new (lifetime , di :: Type :: of :: < Self > ()) ",
+            ". depends_on (di :: ServiceDependency :: new (di :: Type :: of :: < < BarRef as :: std :: ops :: Deref > :: Target > () , di :: ServiceCardinality :: ExactlyOne)) ",
+            ". from (| sp : & di :: ServiceProvider | di :: ServiceRef :: new (Self :: new (sp . get_required :: < < BarRef as :: std :: ops :: Deref > :: Target > ()))) ",
+            "} ",
+            "}");
+
+        assert_eq!(expected, result.to_string());
+    }
+
+    #[test]
+    fn attribute_should_not_recognize_user_defined_vec() {
+        // arrange
+        let metadata = TokenStream::from_str(r#"Foo"#).unwrap();
+        let input = TokenStream::from_str(
+            r#"
+            impl FooImpl {
+                fn new(_bars: collections::Vec<Rc<dyn Bar>>) -> Self {
+                    Self { }
+                }
+            }
+        "#,
+        )
+        .unwrap();
+
+        // act
+        let result = _injectable(metadata, input);
+
+        // assert
+        assert!(result
+            .to_string()
+            .starts_with("compile_error ! { \"Expected ServiceRef, Rc, or Arc."));
+    }
+
+    #[test]
+    fn attribute_should_not_recognize_user_defined_lazy() {
+        // arrange
+        let metadata = TokenStream::from_str(r#"Foo"#).unwrap();
+        let input = TokenStream::from_str(
+            r#"
+            impl FooImpl {
+                fn new(_bar: other::Lazy<Rc<dyn Bar>>) -> Self {
+                    Self { }
+                }
+            }
+        "#,
+        )
+        .unwrap();
+
+        // act
+        let result = _injectable(metadata, input);
+
+        // assert
+        assert!(result
+            .to_string()
+            .starts_with("compile_error ! { \"Expected ServiceRef, Rc, or Arc."));
+    }
+
+    #[test]
+    fn attribute_should_not_recognize_user_defined_factory() {
+        // arrange
+        let metadata = TokenStream::from_str(r#"Foo"#).unwrap();
+        let input = TokenStream::from_str(
+            r#"
+            impl FooImpl {
+                fn new(_bar: other::Factory<Rc<dyn Bar>>) -> Self {
+                    Self { }
+                }
+            }
+        "#,
+        )
+        .unwrap();
+
+        // act
+        let result = _injectable(metadata, input);
+
+        // assert
+        assert!(result
+            .to_string()
+            .starts_with("compile_error ! { \"Expected ServiceRef, Rc, or Arc."));
+    }
+
+    #[test]
+    fn attribute_should_inject_values() {
+        // arrange
+        let metadata = TokenStream::new();
+        let input = TokenStream::from_str(
+            r#"
+            impl Server {
+                fn new(
+                    _bar: Rc<Bar>,
+                    #[inject(value = 8080)] _port: u16,
+                    #[inject(default)] _timeout: Duration,
+                    #[inject(from = "config::name")] _name: String) -> Self {
+                    Self { }
+                }
+            }
+        "#,
+        )
+        .unwrap();
+
+        // act
+        let result = _injectable(metadata, input);
+
+        // assert
+        let expected = concat!(
+            "impl Server { ",
+            "fn new (_bar : Rc < Bar > , _port : u16 , _timeout : Duration , _name : String) -> Self { ",
+            "Self { } ",
+            "} ",
+            "} ",
+            "impl di :: Injectable for Server { ",
+            "# [track_caller] fn inject (lifetime : di :: ServiceLifetime) -> di :: ServiceDescriptor { ",
+            "di :: ServiceDescriptorBuilder :: < Self , Self > :: new (lifetime , di :: Type :: of :: < Self > ()) ",
+            ". depends_on (di :: ServiceDependency :: new (di :: Type :: of :: < Bar > () , di :: ServiceCardinality :: ExactlyOne)) ",
+            ". from (| sp : & di :: ServiceProvider | di :: ServiceRef :: new (Self :: new (",
+            "sp . get_required :: < Bar > () , ",
+            "8080 , ",
+            ":: std :: default :: Default :: default () , ",
+            "config :: name (sp)))) ",
+            "} ",
+            "}");
+
+        assert_eq!(expected, result.to_string());
+    }
+
+    #[test]
+    fn attribute_should_report_all_invalid_arguments() {
+        // arrange
+        let metadata = TokenStream::new();
+        let input = TokenStream::from_str(
+            r#"
+            impl Foo {
+                fn new(_port: u16, _bar: Rc<Bar>, _bars: Option<Vec<Rc<Bar>>>) -> Self {
+                    Self { }
+                }
+            }
+        "#,
+        )
+        .unwrap();
+
+        // act
+        let result = _injectable(metadata, input);
+
+        // assert
+        let output = result.to_string();
+        assert_eq!(output.matches("compile_error").count(), 2);
+        assert!(output.contains("Expected ServiceRef, Rc, or Arc."));
+        assert!(output.contains("Option<Vec> is not supported. Did you mean Vec?"));
+    }
+
+    #[test]
+    fn attribute_should_inject_lazy_dependencies() {
+        // arrange
+        let metadata = TokenStream::from_str(r#"Foo"#).unwrap();
+        let input = TokenStream::from_str(
+            r#"
+            impl FooImpl {
+                fn new(
+                    _bar: Lazy<Rc<dyn Bar>>,
+                    _baz: Lazy<Option<Rc<Baz>>>,
+                    _bars: di::lazy::Lazy<Vec<Rc<dyn Bar>>>) -> Self {
+                    Self { }
+                }
+            }
+        "#,
+        )
+        .unwrap();
+
+        // act
+        let result = _injectable(metadata, input);
+
+        // assert
+        let expected = concat!(
+            "impl di :: Injectable for FooImpl { ",
+            "# [track_caller] fn inject (lifetime : di :: ServiceLifetime) -> di :: ServiceDescriptor { ",
+            "di :: ServiceDescriptorBuilder :: < dyn Foo , Self > :: new (lifetime , di :: Type :: of :: < Self > ()) ",
+            ". depends_on (di :: ServiceDependency :: new_lazy (di :: Type :: of :: < dyn Bar > () , di :: ServiceCardinality :: ExactlyOne)) ",
+            ". depends_on (di :: ServiceDependency :: new_lazy (di :: Type :: of :: < Baz > () , di :: ServiceCardinality :: ZeroOrOne)) ",
+            ". depends_on (di :: ServiceDependency :: new_lazy (di :: Type :: of :: < dyn Bar > () , di :: ServiceCardinality :: ZeroOrMore)) ",
+            ". from (| sp : & di :: ServiceProvider | di :: ServiceRef :: new (Self :: new (",
+            "di :: lazy :: exactly_one :: < dyn Bar > (sp . clone ()) , ",
+            "di :: lazy :: zero_or_one :: < Baz > (sp . clone ()) , ",
+            "di :: lazy :: zero_or_more :: < dyn Bar > (sp . clone ())))) ",
+            "} ",
+            "}");
+
+        assert!(result.to_string().ends_with(expected));
+    }
+
+    #[test]
+    fn attribute_should_inject_factory_dependency() {
+        // arrange
+        let metadata = TokenStream::from_str(r#"Foo"#).unwrap();
+        let input = TokenStream::from_str(
+            r#"
+            impl FooImpl {
+                fn new(_bar: Factory<Rc<dyn Bar>>) -> Self {
+                    Self { }
+                }
+            }
+        "#,
+        )
+        .unwrap();
+
+        // act
+        let result = _injectable(metadata, input);
+
+        // assert
+        let expected = concat!(
+            "impl di :: Injectable for FooImpl { ",
+            "# [track_caller] fn inject (lifetime : di :: ServiceLifetime) -> di :: ServiceDescriptor { ",
+            "di :: ServiceDescriptorBuilder :: < dyn Foo , Self > :: new (lifetime , di :: Type :: of :: < Self > ()) ",
+            ". depends_on (di :: ServiceDependency :: new_lazy (di :: Type :: of :: < dyn Bar > () , di :: ServiceCardinality :: ExactlyOne)) ",
+            ". from (| sp : & di :: ServiceProvider | di :: ServiceRef :: new (Self :: new (",
+            "di :: lazy :: exactly_one_factory :: < dyn Bar > (sp . clone ())))) ",
+            "} ",
+            "}");
+
+        assert!(result.to_string().ends_with(expected));
+    }
+
+    #[test]
+    fn attribute_should_inject_runtime_arguments() {
+        // arrange
+        let metadata = TokenStream::from_str(r#"Foo"#).unwrap();
+        let input = TokenStream::from_str(
+            r#"
+            impl FooImpl {
+                fn new(_bar: Rc<dyn Bar>, #[inject(arg)] _tenant: String, #[inject(arg)] _path: String) -> Self {
+                    Self { }
+                }
+            }
+        "#,
+        )
+        .unwrap();
+
+        // act
+        let result = _injectable(metadata, input);
+
+        // assert
+        let expected = concat!(
+            "impl di :: ParamInjectable for FooImpl { ",
+            "# [track_caller] fn inject (lifetime : di :: ServiceLifetime) -> di :: ServiceDescriptor { ",
+            "assert ! (lifetime == di :: ServiceLifetime :: Transient || lifetime == di :: ServiceLifetime :: Scoped , ",
+            "\"The factory for '{}' must have a transient or scoped lifetime.\" , ",
+            ":: std :: any :: type_name :: < Self > ()) ; ",
+            "let factory : di :: ServiceRef < di :: ParamServiceFactory < (String , String) , dyn Foo >> = ",
+            "di :: ServiceRef :: new (| sp : & di :: ServiceProvider , (arg1 , arg2) : (String , String) | ",
+            "-> di :: ServiceRef < dyn Foo > { ",
+            "di :: ServiceRef :: new (Self :: new (sp . get_required :: < dyn Bar > () , arg1 , arg2)) ",
+            "}) ; ",
+            "di :: ServiceDescriptorBuilder :: < di :: ParamFactory < (String , String) , dyn Foo > , Self > :: new (lifetime , di :: Type :: of :: < Self > ()) ",
+            ". depends_on (di :: ServiceDependency :: new (di :: Type :: of :: < dyn Bar > () , di :: ServiceCardinality :: ExactlyOne)) ",
+            ". from (move | sp : & di :: ServiceProvider | di :: ServiceRef :: new (di :: ParamFactory :: new (sp . clone () , factory . clone ()))) ",
+            "} ",
+            "}");
+
+        assert!(result.to_string().ends_with(expected));
+    }
 }
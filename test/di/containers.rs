@@ -17,6 +17,10 @@ impl Container {
     pub fn foo(&self) -> ServiceRef<dyn Foo> {
         self.provider.get_required::<dyn Foo>()
     }
+
+    pub fn has_foo(&self) -> bool {
+        self.provider.is_registered::<dyn Foo>()
+    }
 }
 
 pub struct ScopedContainer {
@@ -8,4 +8,5 @@
 mod traits;
 mod structs;
 mod containers;
-mod scenarios;
\ No newline at end of file
+mod scenarios;
+mod ui;
\ No newline at end of file
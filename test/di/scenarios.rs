@@ -64,7 +64,7 @@ fn inject_should_implement_struct_for_self_with_dependency() {
 }
 
 #[test]
-#[allow(clippy::vtable_address_comparisons)]
+#[allow(ambiguous_wide_pointer_comparisons)]
 fn inject_should_clone_service_provider_and_return_same_singleton() {
     // arrange
     let provider = ServiceCollection::new()
@@ -84,7 +84,23 @@ fn inject_should_clone_service_provider_and_return_same_singleton() {
 }
 
 #[test]
-#[allow(clippy::vtable_address_comparisons)]
+fn container_should_check_registration_without_instantiating_service() {
+    // arrange
+    let provider = ServiceCollection::new()
+        .add(containers::Container::transient())
+        .build_provider()
+        .unwrap();
+    let container = provider.get_required::<containers::Container>();
+
+    // act
+    let registered = container.has_foo();
+
+    // assert
+    assert!(!registered);
+}
+
+#[test]
+#[allow(ambiguous_wide_pointer_comparisons)]
 fn inject_should_clone_service_provider_and_return_different_scoped_instance() {
     // arrange
     let provider = ServiceCollection::new()
@@ -165,6 +181,38 @@ fn inject_should_implement_lazy_struct() {
     assert_eq!("Success!", foo.echo())
 }
 
+#[test]
+fn inject_should_not_treat_user_defined_lazy_as_lazy() {
+    // arrange
+    let provider = ServiceCollection::new()
+        .add(structs::Bar::transient())
+        .add(structs::OtherLazyFoo::transient())
+        .build_provider()
+        .unwrap();
+
+    // act
+    let foo = provider.get_required::<structs::OtherLazyFoo>();
+
+    // assert
+    assert_eq!("Success!", foo.echo())
+}
+
+#[test]
+fn inject_should_not_treat_user_defined_factory_as_factory() {
+    // arrange
+    let provider = ServiceCollection::new()
+        .add(structs::Bar::transient())
+        .add(structs::OtherFactoryFoo::transient())
+        .build_provider()
+        .unwrap();
+
+    // act
+    let foo = provider.get_required::<structs::OtherFactoryFoo>();
+
+    // assert
+    assert_eq!("Success!", foo.echo())
+}
+
 #[test]
 fn inject_should_implement_required_lazy_trait() {
     // arrange
@@ -227,3 +275,172 @@ fn inject_should_implement_many_lazy_trait() {
     // assert
     assert_eq!("Success!", foo.echo())
 }
+
+#[test]
+fn inject_should_implement_struct_using_crate_path() {
+    // arrange
+    let provider = ServiceCollection::new()
+        .add(structs::Baz::transient())
+        .build_provider()
+        .unwrap();
+
+    // act
+    let baz = provider.get_required::<structs::Baz>();
+
+    // assert
+    assert_eq!("Success!", baz.echo())
+}
+
+#[test]
+fn inject_should_implement_trait_with_aliased_dependency() {
+    // arrange
+    let provider = ServiceCollection::new()
+        .add(traits::BarImpl::transient())
+        .add(traits::AliasedFoo::transient())
+        .build_provider()
+        .unwrap();
+
+    // act
+    let foo = provider.get_required::<dyn traits::Foo>();
+
+    // assert
+    assert_eq!("Success!", foo.echo())
+}
+
+#[test]
+fn inject_should_implement_trait_with_qualified_dependencies() {
+    // arrange
+    let provider = ServiceCollection::new()
+        .add(traits::BarImpl::transient())
+        .add(traits::QualifiedFoo::transient())
+        .build_provider()
+        .unwrap();
+
+    // act
+    let foo = provider.get_required::<dyn traits::Foo>();
+
+    // assert
+    assert_eq!("Success!", foo.echo())
+}
+
+#[test]
+fn inject_should_implement_struct_with_values() {
+    // arrange
+    let mut services = ServiceCollection::new();
+
+    services
+        .add(structs::Bar::transient())
+        .add(structs::Server::transient());
+
+    let provider = services.build_provider().unwrap();
+
+    // act
+    let server = provider.get_required::<structs::Server>();
+
+    // assert
+    assert_eq!(services[1].dependencies().len(), 1);
+    assert_eq!("Success! localhost:8080 0s", server.echo());
+}
+
+#[test]
+fn inject_should_allow_circular_dependency_through_lazy_dependency() {
+    // arrange
+    let provider = ServiceCollection::new()
+        .add(structs::Ping::transient())
+        .add(structs::Pong::transient())
+        .build_provider()
+        .unwrap();
+
+    // act
+    let ping = provider.get_required::<structs::Ping>();
+
+    // assert
+    assert_eq!("Pong!", ping.echo())
+}
+
+#[test]
+#[allow(ambiguous_wide_pointer_comparisons)]
+fn inject_should_create_new_instance_from_factory() {
+    // arrange
+    let provider = ServiceCollection::new()
+        .add(traits::BarImpl::transient())
+        .add(traits::FactoryFoo::singleton())
+        .build_provider()
+        .unwrap();
+    let foo = provider.get_required::<traits::FactoryFoo>();
+
+    // act
+    let bar1 = foo.bar();
+    let bar2 = foo.bar();
+
+    // assert
+    assert!(!ServiceRef::ptr_eq(&bar1, &bar2));
+}
+
+#[test]
+fn inject_should_create_service_with_runtime_argument() {
+    // arrange
+    let provider = ServiceCollection::new()
+        .add(traits::BarImpl::transient())
+        .add(traits::TenantFoo::transient())
+        .add(traits::TenantRegistry::singleton())
+        .build_provider()
+        .unwrap();
+    let registry = provider.get_required::<traits::TenantRegistry>();
+
+    // act
+    let tenant1 = registry.tenant("contoso");
+    let tenant2 = registry.tenant("fabrikam");
+
+    // assert
+    assert_eq!(&tenant1.tenant, "contoso");
+    assert_eq!(&tenant2.tenant, "fabrikam");
+    assert_eq!(tenant1.bar.echo(), "Success!");
+}
+
+#[test]
+#[should_panic(expected = "must have a transient or scoped lifetime")]
+fn inject_should_not_allow_singleton_factory_with_runtime_argument() {
+    // arrange
+    let lifetime = ServiceLifetime::Singleton;
+
+    // act
+    let _ = traits::TenantFoo::inject(lifetime);
+
+    // assert
+    // panics
+}
+
+#[test]
+fn inject_should_order_services() {
+    // arrange
+    let provider = ServiceCollection::new()
+        .add(traits::Respond::transient())
+        .add(traits::Authenticate::transient())
+        .build_provider()
+        .unwrap();
+
+    // act
+    let names: Vec<_> = provider
+        .get_all::<dyn traits::Handler>()
+        .map(|h| h.name().to_owned())
+        .collect();
+
+    // assert
+    assert_eq!(names, ["authenticate", "respond"]);
+}
+
+#[test]
+fn inject_should_record_caller_as_origin() {
+    // arrange
+    let line = line!() + 3;
+
+    // act
+    let descriptors = [traits::BarImpl::transient(), traits::TenantFoo::transient()];
+
+    // assert
+    for descriptor in &descriptors {
+        assert_eq!(descriptor.origin().file(), file!());
+        assert_eq!(descriptor.origin().line(), line);
+    }
+}
@@ -1,5 +1,6 @@
-use di::{inject, injectable, lazy::Lazy, ServiceRef};
+use di::{inject, injectable, lazy::Lazy, ServiceProvider, ServiceRef};
 use std::marker::PhantomData;
+use std::time::Duration;
 
 pub struct Bar;
 
@@ -88,3 +89,129 @@ impl LazyFoo {
         self.bar.value().echo()
     }
 }
+
+pub mod other {
+    use di::ServiceRef;
+
+    // user-defined types that share their names with di types
+    pub type Lazy<T> = ServiceRef<T>;
+    pub type Factory<T> = ServiceRef<T>;
+}
+
+pub struct OtherLazyFoo {
+    bar: other::Lazy<Bar>,
+}
+
+#[injectable]
+impl OtherLazyFoo {
+    // only Lazy from the lazy module is deferred
+    pub fn new(#[inject(service)] bar: other::Lazy<Bar>) -> Self {
+        Self { bar }
+    }
+
+    pub fn echo(&self) -> &str {
+        self.bar.echo()
+    }
+}
+
+pub struct OtherFactoryFoo {
+    bar: other::Factory<Bar>,
+}
+
+#[injectable]
+impl OtherFactoryFoo {
+    // only Factory from the lazy module creates new instances
+    pub fn new(#[inject(service)] bar: other::Factory<Bar>) -> Self {
+        Self { bar }
+    }
+
+    pub fn echo(&self) -> &str {
+        self.bar.echo()
+    }
+}
+
+pub mod facade {
+    pub use di;
+}
+
+pub struct Baz;
+
+// make Baz injectable through a re-exported crate
+#[injectable(crate = "crate::structs::facade::di")]
+impl Baz {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn echo(&self) -> &str {
+        "Success!"
+    }
+}
+
+pub struct Server {
+    bar: ServiceRef<Bar>,
+    port: u16,
+    timeout: Duration,
+    name: String,
+}
+
+fn server_name(_services: &ServiceProvider) -> String {
+    String::from("localhost")
+}
+
+#[injectable]
+impl Server {
+    pub fn new(
+        bar: ServiceRef<Bar>,
+        #[inject(value = 8080)] port: u16,
+        #[inject(default)] timeout: Duration,
+        #[inject(from = "server_name")] name: String,
+    ) -> Self {
+        Self {
+            bar,
+            port,
+            timeout,
+            name,
+        }
+    }
+
+    pub fn echo(&self) -> String {
+        format!(
+            "{} {}:{} {}s",
+            self.bar.echo(),
+            self.name,
+            self.port,
+            self.timeout.as_secs()
+        )
+    }
+}
+
+pub struct Ping {
+    pong: Lazy<ServiceRef<Pong>>,
+}
+
+#[injectable]
+impl Ping {
+    pub fn new(pong: Lazy<ServiceRef<Pong>>) -> Self {
+        Self { pong }
+    }
+
+    pub fn echo(&self) -> &str {
+        self.pong.value().echo()
+    }
+}
+
+pub struct Pong {
+    _ping: ServiceRef<Ping>,
+}
+
+#[injectable]
+impl Pong {
+    pub fn new(ping: ServiceRef<Ping>) -> Self {
+        Self { _ping: ping }
+    }
+
+    pub fn echo(&self) -> &str {
+        "Pong!"
+    }
+}
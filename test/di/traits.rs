@@ -1,4 +1,8 @@
-use di::{inject, injectable, lazy::Lazy, ServiceRef};
+use di::{
+    inject, injectable,
+    lazy::{Factory, Lazy},
+    ParamFactory, ServiceRef,
+};
 use std::fmt::Debug;
 
 pub trait Foo {
@@ -148,3 +152,123 @@ impl ManyLazyFoo {
         Self { bars }
     }
 }
+
+pub type BarRef = ServiceRef<dyn Bar>;
+
+pub struct AliasedFoo {
+    bar: BarRef,
+}
+
+impl Foo for AliasedFoo {
+    fn echo(&self) -> &str {
+        self.bar.echo()
+    }
+}
+
+#[injectable(Foo)]
+impl AliasedFoo {
+    // type aliases cannot be inspected; opt-in to service resolution
+    pub fn new(#[inject(service)] bar: BarRef) -> Self {
+        Self { bar }
+    }
+}
+
+pub struct QualifiedFoo {
+    bars: std::vec::Vec<di::ServiceRef<dyn Bar>>,
+}
+
+impl Foo for QualifiedFoo {
+    fn echo(&self) -> &str {
+        self.bars[0].echo()
+    }
+}
+
+#[injectable(Foo)]
+impl QualifiedFoo {
+    pub fn new(
+        bars: std::vec::Vec<di::ServiceRef<dyn Bar>>,
+        _provider: di::ServiceProvider,
+    ) -> Self {
+        Self { bars }
+    }
+}
+
+pub struct FactoryFoo {
+    bar: Factory<ServiceRef<dyn Bar>>,
+}
+
+impl FactoryFoo {
+    pub fn bar(&self) -> ServiceRef<dyn Bar> {
+        self.bar.create()
+    }
+}
+
+#[injectable]
+impl FactoryFoo {
+    pub fn new(bar: Factory<ServiceRef<dyn Bar>>) -> Self {
+        Self { bar }
+    }
+}
+
+pub struct TenantFoo {
+    pub bar: ServiceRef<dyn Bar>,
+    pub tenant: String,
+}
+
+#[injectable]
+impl TenantFoo {
+    pub fn new(bar: ServiceRef<dyn Bar>, #[inject(arg)] tenant: String) -> Self {
+        Self { bar, tenant }
+    }
+}
+
+pub struct TenantRegistry {
+    tenants: ServiceRef<ParamFactory<String, TenantFoo>>,
+}
+
+impl TenantRegistry {
+    pub fn tenant(&self, id: &str) -> ServiceRef<TenantFoo> {
+        self.tenants.create(id.to_owned())
+    }
+}
+
+#[injectable]
+impl TenantRegistry {
+    pub fn new(tenants: ServiceRef<ParamFactory<String, TenantFoo>>) -> Self {
+        Self { tenants }
+    }
+}
+
+pub trait Handler {
+    fn name(&self) -> &str;
+}
+
+pub struct Authenticate;
+
+impl Handler for Authenticate {
+    fn name(&self) -> &str {
+        "authenticate"
+    }
+}
+
+#[injectable(Handler, order = -10)]
+impl Authenticate {
+    fn new() -> Self {
+        Self {}
+    }
+}
+
+pub struct Respond;
+
+impl Handler for Respond {
+    fn name(&self) -> &str {
+        "respond"
+    }
+}
+
+#[injectable(Handler)]
+impl Respond {
+    fn new() -> Self {
+        Self {}
+    }
+}
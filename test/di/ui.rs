@@ -0,0 +1,9 @@
+// the expected compiler output for each case is defined in a corresponding .stderr file.
+// to regenerate the expected output after an intentional change, run:
+//
+// TRYBUILD=overwrite cargo test -p more-di-tests ui
+#[test]
+fn injectable_should_report_compile_errors() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("ui/*.rs");
+}
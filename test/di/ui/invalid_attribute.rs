@@ -0,0 +1,12 @@
+use di::injectable;
+
+pub struct Foo;
+
+#[injectable(Foo, crate = "di" Bar)]
+impl Foo {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use di::injectable;
+
+pub struct Foo;
+
+#[injectable]
+impl Foo {
+    pub fn create() -> Self {
+        Self {}
+    }
+}
+
+fn main() {}
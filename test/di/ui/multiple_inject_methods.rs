@@ -0,0 +1,18 @@
+use di::injectable;
+
+pub struct Foo;
+
+#[injectable]
+impl Foo {
+    #[di::inject]
+    pub fn create() -> Self {
+        Self {}
+    }
+
+    #[di::inject]
+    pub fn create_new() -> Self {
+        Self {}
+    }
+}
+
+fn main() {}
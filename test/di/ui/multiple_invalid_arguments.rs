@@ -0,0 +1,18 @@
+use di::injectable;
+
+pub struct Bar;
+pub struct Foo;
+
+#[injectable]
+impl Foo {
+    pub fn new(
+        _bar: di::ServiceRef<Bar>,
+        _port: u16,
+        _bars: Option<Vec<di::ServiceRef<Bar>>>,
+        _name: &str,
+    ) -> Self {
+        Self {}
+    }
+}
+
+fn main() {}
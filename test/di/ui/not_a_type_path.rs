@@ -0,0 +1,12 @@
+use di::injectable;
+
+pub trait Foo {}
+
+#[injectable]
+impl dyn Foo {
+    pub fn new() -> Box<Self> {
+        unimplemented!()
+    }
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use di::injectable;
+
+#[injectable]
+pub fn new() {}
+
+fn main() {}
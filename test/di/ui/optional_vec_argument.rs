@@ -0,0 +1,13 @@
+use di::injectable;
+
+pub struct Bar;
+pub struct Foo;
+
+#[injectable]
+impl Foo {
+    pub fn new(_bars: Option<Vec<di::ServiceRef<Bar>>>) -> Self {
+        Self {}
+    }
+}
+
+fn main() {}
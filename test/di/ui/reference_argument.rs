@@ -0,0 +1,13 @@
+use di::injectable;
+
+pub struct Bar;
+pub struct Foo;
+
+#[injectable]
+impl Foo {
+    pub fn new(_bar: &Bar) -> Self {
+        Self {}
+    }
+}
+
+fn main() {}
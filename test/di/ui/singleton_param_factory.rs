@@ -0,0 +1,16 @@
+use di::*;
+
+pub struct Tenant {
+    pub id: String,
+}
+
+#[injectable]
+impl Tenant {
+    pub fn new(#[inject(arg)] id: String) -> Self {
+        Self { id }
+    }
+}
+
+fn main() {
+    let _ = Tenant::singleton();
+}
@@ -0,0 +1,12 @@
+use di::injectable;
+
+pub struct Foo;
+
+#[injectable]
+impl Foo {
+    pub fn new(#[inject(optional)] _port: u16) -> Self {
+        Self {}
+    }
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use di::injectable;
+
+pub struct Foo;
+
+#[injectable]
+impl Foo {
+    pub fn new(_values: di::ServiceRef<[u8]>, _bars: Vec<&str>) -> Self {
+        Self {}
+    }
+}
+
+fn main() {}